@@ -0,0 +1,547 @@
+//! The catalog of all creature parts
+//!
+//! Everything the game needs to know about each kind of part
+//! (sprites, names, editor labels, descriptions, stats) is declared here.
+//! To add a new part, draw it at the end of its sprite sheet
+//! and extend the respective category below.
+//!
+//! Each part has a [`Rarity`] tier,
+//! which sets how often it comes up in random creatures
+//! (see [`Rarity::weight`]),
+//! unless the category overrides the weight of that part
+//! (see [`PartCategory::weights`]).
+
+use core::ops::Range;
+
+use crate::stats::NUM_STATS;
+
+// (short names for the rarities in the catalog below)
+use Rarity::{Common, Legendary, Rare, Uncommon};
+
+/// A category of creature parts
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[repr(u8)]
+pub enum PartKind {
+    Shape,
+    Color,
+    Pattern,
+    Eyes,
+    Mouth,
+    Headwear,
+    Legs,
+    Arms,
+    Tail,
+    Secondary,
+}
+
+impl PartKind {
+    /// all part categories, in the order shown in the editor
+    pub const ALL: [PartKind; NUM_PART_KINDS] = [
+        PartKind::Shape,
+        PartKind::Color,
+        PartKind::Secondary,
+        PartKind::Pattern,
+        PartKind::Eyes,
+        PartKind::Mouth,
+        PartKind::Headwear,
+        PartKind::Legs,
+        PartKind::Arms,
+        PartKind::Tail,
+    ];
+
+    /// the catalog entry for this category
+    pub fn category(self) -> &'static PartCategory {
+        &CATALOG.categories[self as usize]
+    }
+
+    /// the number of parts available in this category
+    pub fn count(self) -> u8 {
+        self.category().count
+    }
+}
+
+pub const NUM_PART_KINDS: usize = 10;
+
+/// How rare a part (or a whole creature) is
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+    Legendary,
+}
+
+impl Rarity {
+    /// The relative chance of a part of this rarity
+    /// being picked for a random creature,
+    /// against the other parts in the same category.
+    ///
+    /// Tune these to make rare parts more or less rare.
+    pub fn weight(self) -> u16 {
+        match self {
+            Rarity::Common => 8,
+            Rarity::Uncommon => 4,
+            Rarity::Rare => 2,
+            Rarity::Legendary => 1,
+        }
+    }
+
+    /// How much a part of this rarity adds to the rarity of a creature
+    /// (see [`Rarity::of_score`]).
+    pub fn score(self) -> u16 {
+        match self {
+            Rarity::Common => 0,
+            Rarity::Uncommon => 1,
+            Rarity::Rare => 2,
+            Rarity::Legendary => 4,
+        }
+    }
+
+    /// The rarity of a creature from the sum of the scores of its parts.
+    ///
+    /// With the weights in the catalog,
+    /// about half of random creatures are common,
+    /// a third uncommon, one in ten rare and one in fifty legendary
+    /// (about 56%, 33%, 9% and 2%).
+    pub fn of_score(score: u16) -> Self {
+        match score {
+            0..=4 => Rarity::Common,
+            5..=7 => Rarity::Uncommon,
+            8..=10 => Rarity::Rare,
+            _ => Rarity::Legendary,
+        }
+    }
+
+    /// the name of the tier, as shown in the game
+    pub fn name(self) -> &'static str {
+        match self {
+            Rarity::Common => "Common",
+            Rarity::Uncommon => "Uncommon",
+            Rarity::Rare => "Rare",
+            Rarity::Legendary => "Legendary",
+        }
+    }
+}
+
+/// Description of one category of creature parts
+#[derive(Debug)]
+pub struct PartCategory {
+    /// the label shown in the editor
+    pub label: &'static str,
+    /// the embedded sprite sheet (PNG, 8-bit indexed),
+    /// with one 32x32 sprite per part side by side,
+    /// or `None` if this category is not drawn with sprites
+    pub sprite_sheet: Option<&'static [u8]>,
+    /// the number of parts in this category
+    pub count: u8,
+    /// the name syllable of each part
+    /// (empty if the category is named in some other way)
+    pub syllables: &'static [&'static str],
+    /// the region of each sprite which is drawn onto the creature,
+    /// as columns and rows
+    pub bounds: (Range<u32>, Range<u32>),
+    /// whether the sprite is only drawn over the creature's body colors
+    /// (palette indices 3 to 6)
+    pub clip_to_body: bool,
+    /// whether the body colors in the sprite (palette indices 3 to 6)
+    /// are drawn with the creature's secondary color instead
+    pub secondary_ramp: bool,
+    /// the rarity of each part
+    pub rarities: &'static [Rarity],
+    /// the relative chance of each part being picked for a random creature,
+    /// overriding the weight of its rarity
+    /// (empty to use [`Rarity::weight`] for every part)
+    pub weights: &'static [u16],
+    /// what the creature's description says about each part,
+    /// following the creature's name or "It"
+    /// (empty if the category is not described)
+    pub lore: &'static [&'static str],
+    /// the stat bonuses of each part,
+    /// as `[speed, strength, smarts, charm, stamina]`
+    /// (empty if the category does not change the stats)
+    ///
+    /// Bonuses may be negative,
+    /// and the stats are kept between 0 and
+    /// [`MAX_STAT`](crate::stats::MAX_STAT) in the end.
+    pub stat_bonuses: &'static [[i8; NUM_STATS]],
+}
+
+impl PartCategory {
+    /// the rarity of a part in this category
+    pub fn rarity(&self, part: u8) -> Rarity {
+        self.rarities
+            .get(part as usize)
+            .copied()
+            .unwrap_or(Rarity::Common)
+    }
+
+    /// the relative chance of a part in this category
+    /// being picked for a random creature
+    pub fn weight(&self, part: u8) -> u16 {
+        self.weights
+            .get(part as usize)
+            .copied()
+            .unwrap_or_else(|| self.rarity(part).weight())
+    }
+}
+
+/// The full catalog of creature parts
+#[derive(Debug)]
+pub struct PartCatalog {
+    /// one entry per part category, indexed by [`PartKind`]
+    pub categories: [PartCategory; NUM_PART_KINDS],
+    /// the order in which the part sprites are drawn,
+    /// from back to front
+    pub layers: &'static [PartKind],
+    /// the main RGB color (in 0..64 range) of each body color
+    pub body_colors: &'static [[u8; 3]],
+    /// the name portion defined by the creature's limbs,
+    /// indexed by arms and then by legs
+    ///
+    /// Careful when changing these:
+    /// no two creatures may end up with the same name
+    /// (unless they only differ in color, see `CreatureParams::parse_name`).
+    /// Clusters starting with `r` clash with the colors ending in `r`,
+    /// and an empty cluster would merge the color with the eyes.
+    pub limb_syllables: &'static [&'static [&'static str]],
+}
+
+pub static CATALOG: PartCatalog = PartCatalog {
+    categories: [
+        PartCategory {
+            label: "Shape",
+            sprite_sheet: Some(include_bytes!("../../resources/creature-shapes.png")),
+            count: 14,
+            // `-` marks where the color syllable goes
+            syllables: &[
+                "Fl-", "D-n", "Bl-", "Em-", "N-n", "Sn-", "Yl-m", "H-", "J-m", "Al-", "V-n", "T-",
+                "B-", "K-",
+            ],
+            bounds: (1..31, 1..31),
+            clip_to_body: false,
+            secondary_ramp: false,
+            rarities: &[
+                Common, Common, Common, Common, Common, Common, Common, Common, Uncommon, Uncommon,
+                Uncommon, Rare, Rare, Legendary,
+            ],
+            weights: &[],
+            lore: &[],
+            stat_bonuses: &[],
+        },
+        PartCategory {
+            label: "Color",
+            sprite_sheet: None,
+            count: 8,
+            syllables: &["a", "e", "ey", "i", "or", "o", "ar", "ur"],
+            bounds: (0..0, 0..0),
+            clip_to_body: false,
+            secondary_ramp: false,
+            rarities: &[
+                Common, Common, Common, Common, Uncommon, Common, Uncommon, Rare,
+            ],
+            weights: &[],
+            // where the creature lives
+            lore: &[
+                "lives high up on snowy mountains",
+                "lives near warm volcanoes",
+                "lives in sunny deserts",
+                "lives deep in the forest",
+                "lives by icy lakes",
+                "lives at the bottom of the sea",
+                "lives in fields of flowers",
+                "lives in muddy burrows",
+            ],
+            stat_bonuses: &[],
+        },
+        PartCategory {
+            label: "Pattern",
+            sprite_sheet: Some(include_bytes!("../../resources/creature-patterns.png")),
+            count: 4,
+            // plain, spots, stripes, belly patch
+            syllables: &["", "s", "z", "th"],
+            bounds: (0..32, 0..32),
+            clip_to_body: true,
+            secondary_ramp: false,
+            rarities: &[Common, Common, Uncommon, Rare],
+            weights: &[],
+            lore: &[],
+            stat_bonuses: &[
+                // plain
+                [0, 0, 0, 0, 0],
+                // spots
+                [0, 0, 0, 1, 0],
+                // stripes
+                [1, 0, 0, 0, 0],
+                // belly patch
+                [0, 0, 0, 0, 1],
+            ],
+        },
+        PartCategory {
+            label: "Eyes",
+            sprite_sheet: Some(include_bytes!("../../resources/creature-eyes.png")),
+            count: 10,
+            syllables: &["i", "o", "ow", "e", "a", "ya", "yo", "u", "oo", "ey"],
+            // we use a tiny trick here, since we do not expect eye pixels around the boundaries
+            bounds: (3..29, 2..25),
+            clip_to_body: false,
+            secondary_ramp: false,
+            rarities: &[
+                Common, Common, Common, Common, Uncommon, Uncommon, Rare, Common, Uncommon,
+                Legendary,
+            ],
+            weights: &[],
+            // what the creature is like
+            lore: &[
+                "is curious about everything",
+                "is always cheerful",
+                "is shy and quiet",
+                "keeps a close eye on everything",
+                "is calm and friendly",
+                "is very mischievous",
+                "is sweet and affectionate",
+                "never misses a thing",
+                "is a bit of a dreamer",
+                "is grumpy in the mornings",
+            ],
+            stat_bonuses: &[
+                // curious
+                [0, 0, 2, 0, 0],
+                // cheerful
+                [0, 0, 0, 2, 1],
+                // shy
+                [0, 0, 1, 1, 0],
+                // one big eye
+                [0, 1, 2, -1, 0],
+                // calm
+                [0, 0, 1, 1, 1],
+                // mischievous
+                [1, 0, 1, 1, 0],
+                // sweet
+                [0, 0, 0, 3, 0],
+                // many eyes
+                [0, 0, 3, -1, 0],
+                // dreamy
+                [-1, 0, 1, 2, 0],
+                // grumpy
+                [0, 2, 1, -2, 0],
+            ],
+        },
+        PartCategory {
+            label: "Mouth",
+            sprite_sheet: Some(include_bytes!("../../resources/creature-mouths.png")),
+            count: 8,
+            syllables: &["n", "ty", "d", "r", "z", "b", "m", "x"],
+            bounds: (2..30, 5..28),
+            clip_to_body: false,
+            secondary_ramp: false,
+            rarities: &[
+                Common, Common, Common, Uncommon, Common, Uncommon, Rare, Legendary,
+            ],
+            weights: &[],
+            // what the creature eats
+            lore: &[
+                "nibbles on leaves and twigs",
+                "eats mostly berries",
+                "will happily eat anything",
+                "crunches on nuts and seeds",
+                "loves fresh fish",
+                "catches bugs with its long tongue",
+                "sips nectar from flowers",
+                "hunts with its sharp fangs",
+            ],
+            stat_bonuses: &[
+                // leaves and twigs
+                [0, 0, 0, 0, 2],
+                // berries
+                [1, 0, 0, 1, 0],
+                // anything
+                [0, 1, 0, 0, 2],
+                // nuts and seeds
+                [0, 2, 0, 0, 0],
+                // fish
+                [1, 0, 1, 0, 0],
+                // bugs
+                [2, 0, 0, 0, 0],
+                // nectar
+                [1, -1, 0, 2, 0],
+                // fangs
+                [0, 3, 0, -1, 0],
+            ],
+        },
+        PartCategory {
+            label: "Headwear",
+            sprite_sheet: Some(include_bytes!("../../resources/creature-headwear.png")),
+            count: 6,
+            // a title which goes before the rest of the name
+            syllables: &["", "La", "Mc", "Sir", "O'", "Ze"],
+            bounds: (0..32, 0..12),
+            clip_to_body: false,
+            secondary_ramp: false,
+            rarities: &[Common, Uncommon, Uncommon, Rare, Uncommon, Legendary],
+            weights: &[],
+            lore: &[],
+            stat_bonuses: &[
+                // nothing on the head
+                [0, 0, 0, 0, 0],
+                // bow
+                [0, 0, 0, 2, 0],
+                // horns
+                [1, 0, 0, 1, 0],
+                // crown
+                [0, 0, 2, 0, 0],
+                // antenna
+                [0, 0, 2, 0, 0],
+                // top hat
+                [0, 0, 1, 2, 0],
+            ],
+        },
+        PartCategory {
+            label: "Legs",
+            sprite_sheet: Some(include_bytes!("../../resources/creature-legs.png")),
+            count: 7,
+            // named together with the arms, see `limb_syllables`
+            syllables: &[],
+            bounds: (0..32, 9..32),
+            clip_to_body: false,
+            secondary_ramp: true,
+            rarities: &[Common, Common, Common, Uncommon, Uncommon, Rare, Legendary],
+            weights: &[],
+            // how the creature moves
+            lore: &[
+                "gets around by bouncing",
+                "walks slowly but steadily",
+                "stomps about on heavy feet",
+                "slides along the ground",
+                "hops on tiny feet",
+                "can run really fast",
+                "scuttles on its many legs",
+            ],
+            stat_bonuses: &[
+                // bouncy
+                [2, 0, 0, 1, 0],
+                // steady
+                [0, 1, 0, 0, 2],
+                // heavy feet
+                [-1, 3, 0, 0, 1],
+                // sliding
+                [-1, 0, 0, 1, 2],
+                // tiny feet
+                [1, -1, 0, 2, 0],
+                // runner
+                [4, 0, 0, 0, -1],
+                // many legs
+                [2, 1, 0, -1, 1],
+            ],
+        },
+        PartCategory {
+            label: "Arms",
+            sprite_sheet: Some(include_bytes!("../../resources/creature-arms.png")),
+            count: 7,
+            // named together with the legs, see `limb_syllables`
+            syllables: &[],
+            bounds: (0..32, 2..32),
+            clip_to_body: false,
+            secondary_ramp: true,
+            rarities: &[Common, Common, Common, Uncommon, Uncommon, Rare, Legendary],
+            weights: &[],
+            lore: &[],
+            stat_bonuses: &[
+                // white paws
+                [0, 2, 0, 0, 1],
+                // tiny arms
+                [1, -1, 0, 2, 0],
+                // flippers
+                [1, 0, 0, 1, 0],
+                // claws
+                [0, 3, 0, 0, 0],
+                // spindly fingers
+                [0, 1, 1, 0, 0],
+                // raised arms
+                [1, 0, 1, 0, 0],
+                // no arms
+                [1, -1, 2, 0, 0],
+            ],
+        },
+        PartCategory {
+            label: "Tail",
+            sprite_sheet: Some(include_bytes!("../../resources/creature-tails.png")),
+            count: 5,
+            syllables: &["", "o", "a", "us", "el"],
+            bounds: (20..32, 8..30),
+            clip_to_body: false,
+            secondary_ramp: true,
+            rarities: &[Common, Common, Uncommon, Rare, Legendary],
+            weights: &[],
+            lore: &[],
+            stat_bonuses: &[
+                // no tail
+                [0, 0, 0, 0, 0],
+                // curled
+                [1, 0, 0, 1, 0],
+                // fluffy
+                [0, 0, 0, 2, 0],
+                // fin
+                [1, 1, 0, 0, 0],
+                // paddle
+                [2, 0, 0, 0, 1],
+            ],
+        },
+        PartCategory {
+            label: "Secondary color",
+            sprite_sheet: None,
+            // 0 is the same as the body color,
+            // then one for each of the body colors
+            count: 9,
+            // only the main color is part of the name,
+            // so names do not tell the secondary colors apart
+            syllables: &[],
+            bounds: (0..0, 0..0),
+            clip_to_body: false,
+            secondary_ramp: false,
+            rarities: &[
+                Common, Uncommon, Uncommon, Uncommon, Uncommon, Uncommon, Uncommon, Uncommon, Rare,
+            ],
+            // about as many one-color creatures as two-tone ones
+            weights: &[32, 4, 4, 4, 4, 4, 4, 4, 2],
+            lore: &[],
+            stat_bonuses: &[],
+        },
+    ],
+    layers: &[
+        PartKind::Tail,
+        PartKind::Legs,
+        PartKind::Shape,
+        PartKind::Pattern,
+        PartKind::Arms,
+        PartKind::Mouth,
+        PartKind::Eyes,
+        PartKind::Headwear,
+    ],
+    body_colors: &[
+        // white
+        [0x3a, 0x3a, 0x3a],
+        // red
+        [0x3c, 0x14, 0x14],
+        // yellow
+        [0x3c, 0x3c, 0x14],
+        // green
+        [0x14, 0x3c, 0x14],
+        // cyan
+        [0x14, 0x3c, 0x3c],
+        // blue
+        [0x16, 0x16, 0x3c],
+        // magenta
+        [0x3c, 0x14, 0x3c],
+        // brown
+        [0x30, 0x20, 0x14],
+    ],
+    limb_syllables: &[
+        &["n", "t", "lk", "d", "p", "b", "tr"],
+        &["sh", "wr", "c", "f", "k", "ss", "h"],
+        &["bh", "ch", "fr", "m", "g", "kk", "gr"],
+        &["ff", "scht", "sk", "ng", "xij", "th", "gl"],
+        &["dr", "yx", "bl", "nj", "dd", "l", "ft"],
+        &["r", "st", "kh", "v", "j", "wh", "br"],
+        &["w", "pl", "ll", "cl", "z", "sw", "gg"],
+    ],
+};
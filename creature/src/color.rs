@@ -0,0 +1,68 @@
+//! Color conversion between 6-bit VGA RGB and HSV
+
+/// A color in HSV,
+/// with the hue in degrees (0..360)
+/// and the saturation and value in the VGA range (0..64)
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Hsv {
+    pub h: u16,
+    pub s: u8,
+    pub v: u8,
+}
+
+impl Hsv {
+    /// Convert a 6-bit RGB color to HSV.
+    pub fn from_rgb(rgb: [u8; 3]) -> Self {
+        let [r, g, b] = rgb.map(|c| c.min(63) as i32);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+
+        let s = if max == 0 { 0 } else { delta * 63 / max };
+        let h = if delta == 0 {
+            0
+        } else if max == r {
+            (60 * (g - b) / delta).rem_euclid(360)
+        } else if max == g {
+            120 + 60 * (b - r) / delta
+        } else {
+            240 + 60 * (r - g) / delta
+        };
+
+        Hsv {
+            h: h as u16,
+            s: s as u8,
+            v: max as u8,
+        }
+    }
+
+    /// Convert the color to 6-bit RGB.
+    pub fn to_rgb(self) -> [u8; 3] {
+        let v = self.v.min(63) as u32;
+        let s = self.s.min(63) as u32;
+        if s == 0 {
+            return [v as u8; 3];
+        }
+
+        let h = self.h as u32 % 360;
+        let f = h % 60;
+        let p = v * (63 - s) / 63;
+        let q = v * (63 * 60 - s * f) / (63 * 60);
+        let t = v * (63 * 60 - s * (60 - f)) / (63 * 60);
+        let (r, g, b) = match h / 60 {
+            0 => (v, t, p),
+            1 => (q, v, p),
+            2 => (p, v, t),
+            3 => (p, q, v),
+            4 => (t, p, v),
+            _ => (v, p, q),
+        };
+        [r as u8, g as u8, b as u8]
+    }
+}
+
+/// The distance between two hues in degrees, going around the circle
+pub fn hue_distance(a: u16, b: u16) -> u16 {
+    let d = a.abs_diff(b) % 360;
+    d.min(360 - d)
+}
@@ -0,0 +1,882 @@
+//! Creature creation logic
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use tinyrand::{RandRange, Seeded, Xorshift};
+
+use crate::catalog::{PartKind, Rarity, CATALOG, NUM_PART_KINDS};
+use crate::color::{hue_distance, Hsv};
+
+/// the parts told about in creature descriptions, in this order:
+/// habitat, diet, movement and temperament
+const DESCRIBED_PARTS: [PartKind; 4] = [
+    PartKind::Color,
+    PartKind::Mouth,
+    PartKind::Legs,
+    PartKind::Eyes,
+];
+
+/// the layout of each version of the creature code format,
+/// starting at version 1:
+/// the number of parts stored and whether there is a custom color
+///
+/// Codes are written in the oldest version (from 2 onwards)
+/// which can hold the creature.
+const CODE_VERSIONS: [(usize, bool); 5] =
+    [(6, false), (9, false), (9, true), (10, false), (10, true)];
+/// the number of bytes for the custom color in a creature code
+const CODE_COLOR_BYTES: usize = 3;
+/// the maximum number of characters in a creature code
+pub const CODE_LEN: usize = code_chars(code_bytes(NUM_PART_KINDS) + CODE_COLOR_BYTES);
+/// the number of characters in a creature code of each layout,
+/// from the shortest (version 1) to the longest
+const CODE_LENGTHS: [usize; 3] = [8, 12, 16];
+/// the parts stored in creature codes, in this order
+/// (older code versions only have the first few)
+const CODE_PARTS: [PartKind; NUM_PART_KINDS] = [
+    PartKind::Shape,
+    PartKind::Color,
+    PartKind::Eyes,
+    PartKind::Mouth,
+    PartKind::Legs,
+    PartKind::Arms,
+    PartKind::Tail,
+    PartKind::Headwear,
+    PartKind::Pattern,
+    PartKind::Secondary,
+];
+/// the characters used in creature codes
+/// (Crockford's base32, no I, L, O or U to avoid confusion)
+const CODE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Error type for when a creature code cannot be decoded
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CodeError {
+    /// the code does not have the right number of characters
+    BadLength(usize),
+    /// the code has a character which is not in the code alphabet
+    BadCharacter(char),
+    /// the checksum does not match, probably a typo
+    BadChecksum,
+    /// the code is of a version which this game does not know
+    UnsupportedVersion(u8),
+    /// a creature parameter is outside of its valid range
+    OutOfRange { param: &'static str, value: u8 },
+}
+
+impl core::fmt::Display for CodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CodeError::BadLength(len) => {
+                let [short, medium, long] = CODE_LENGTHS;
+                write!(
+                    f,
+                    "code must have {short}, {medium} or {long} characters, but it has {len}"
+                )
+            }
+            CodeError::BadCharacter(c) => write!(f, "invalid character {c:?} in code"),
+            CodeError::BadChecksum => f.write_str("checksum mismatch, please check for typos"),
+            CodeError::UnsupportedVersion(v) => write!(f, "unsupported code version {v}"),
+            CodeError::OutOfRange { param, value } => {
+                write!(f, "{param} #{value} does not exist")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct CreatureParams {
+    /// parameter 1: shape
+    pub shape: u8,
+    /// parameter 2: color
+    pub color: u8,
+    /// parameter 3: eyes
+    pub eyes: u8,
+    /// parameter 4: mouth
+    pub mouth: u8,
+    /// parameter 5: legs
+    pub legs: u8,
+    /// parameter 6: arms
+    pub arms: u8,
+    /// parameter 7: tail (0 is no tail)
+    pub tail: u8,
+    /// parameter 8: headwear (0 is no headwear)
+    pub headwear: u8,
+    /// parameter 9: body pattern (0 is plain)
+    pub pattern: u8,
+    /// parameter 10: secondary color of the limbs and tail
+    /// (0 is the same as the body, otherwise param2 + 1)
+    pub secondary: u8,
+    /// custom body color (6-bit RGB) picked by the player,
+    /// replacing the one from parameter 2
+    /// (which is then the nearest named color)
+    pub custom_color: Option<[u8; 3]>,
+}
+
+impl CreatureParams {
+    /// Create a random creature,
+    /// where rarer parts come up less often.
+    pub fn new_random(rng: &mut impl RandRange<u16>) -> Self {
+        let mut creature = CreatureParams::default();
+        for kind in PartKind::ALL {
+            *creature.part_mut(kind) = random_part(kind, rng);
+        }
+        creature
+    }
+
+    /// the overall rarity of the creature,
+    /// from the rarity of each of its parts
+    pub fn rarity(&self) -> Rarity {
+        let score = PartKind::ALL
+            .iter()
+            .map(|&kind| kind.category().rarity(self.part(kind)).score())
+            .sum();
+        Rarity::of_score(score)
+    }
+
+    /// a short flavor text about the creature
+    /// (always the same for the same creature)
+    pub fn description(&self) -> Description<'_> {
+        Description(self)
+    }
+
+    /// The creature of the day for the given date,
+    /// the same for everyone playing on that day.
+    pub fn of_the_day(year: u16, month: u8, day: u8) -> Self {
+        let day_number = year as u64 * 10_000 + month as u64 * 100 + day as u64;
+        let mut rng =
+            Xorshift::seed(0x0da1_1c4e_a7c4_e5ed ^ day_number.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        Self::new_random(&mut rng)
+    }
+
+    /// Breed this creature with another one into an offspring.
+    ///
+    /// Each part is inherited from either parent,
+    /// then mutates into a random part
+    /// with a chance of 1 in `mutation_odds` (0 for no mutations).
+    pub fn breed(
+        &self,
+        other: &CreatureParams,
+        mutation_odds: u16,
+        rng: &mut impl RandRange<u16>,
+    ) -> Self {
+        let mut offspring = CreatureParams::default();
+        for kind in PartKind::ALL {
+            let parent = if rng.next_range(0..2) == 0 {
+                self
+            } else {
+                other
+            };
+            *offspring.part_mut(kind) = parent.part(kind);
+            if kind == PartKind::Color {
+                // a custom color goes along with the color
+                offspring.custom_color = parent.custom_color;
+            }
+
+            if mutation_odds > 0 && rng.next_range(0..mutation_odds) == 0 {
+                *offspring.part_mut(kind) = random_part(kind, rng);
+                if kind == PartKind::Color {
+                    offspring.custom_color = None;
+                }
+            }
+        }
+        offspring
+    }
+
+    /// the part of the given category
+    pub fn part(&self, kind: PartKind) -> u8 {
+        match kind {
+            PartKind::Shape => self.shape,
+            PartKind::Color => self.color,
+            PartKind::Eyes => self.eyes,
+            PartKind::Mouth => self.mouth,
+            PartKind::Legs => self.legs,
+            PartKind::Arms => self.arms,
+            PartKind::Tail => self.tail,
+            PartKind::Headwear => self.headwear,
+            PartKind::Pattern => self.pattern,
+            PartKind::Secondary => self.secondary,
+        }
+    }
+
+    /// mutable access to the part of the given category
+    pub fn part_mut(&mut self, kind: PartKind) -> &mut u8 {
+        match kind {
+            PartKind::Shape => &mut self.shape,
+            PartKind::Color => &mut self.color,
+            PartKind::Eyes => &mut self.eyes,
+            PartKind::Mouth => &mut self.mouth,
+            PartKind::Legs => &mut self.legs,
+            PartKind::Arms => &mut self.arms,
+            PartKind::Tail => &mut self.tail,
+            PartKind::Headwear => &mut self.headwear,
+            PartKind::Pattern => &mut self.pattern,
+            PartKind::Secondary => &mut self.secondary,
+        }
+    }
+
+    /// Swap the part of the given category for the next one
+    /// (or the previous one if `backwards`),
+    /// wrapping around at the ends.
+    pub fn cycle_part(&mut self, kind: PartKind, backwards: bool) {
+        if kind == PartKind::Color {
+            // back to the named colors
+            self.custom_color = None;
+        }
+        let count = kind.count();
+        let part = self.part_mut(kind);
+        *part = if backwards {
+            (*part + count - 1) % count
+        } else {
+            (*part + 1) % count
+        };
+    }
+
+    /// maps param2 (or the custom color) to the main RGB color (in 0..64 range)
+    pub fn body_color(&self) -> [u8; 3] {
+        if let Some(rgb) = self.custom_color {
+            return rgb;
+        }
+        CATALOG
+            .body_colors
+            .get(self.color as usize)
+            .copied()
+            // fallback to grey
+            .unwrap_or([0x1f, 0x1f, 0x1f])
+    }
+
+    /// Set a custom body color,
+    /// naming the creature after the nearest named color.
+    pub fn set_custom_color(&mut self, rgb: [u8; 3]) {
+        self.custom_color = Some(rgb.map(|c| c.min(63)));
+        self.color = nearest_named_color(rgb);
+    }
+
+    /// maps param10 to the RGB color (in 0..64 range) of the limbs and tail
+    pub fn secondary_color(&self) -> [u8; 3] {
+        match self.secondary {
+            0 => self.body_color(),
+            i => CATALOG
+                .body_colors
+                .get(i as usize - 1)
+                .copied()
+                // fallback to grey
+                .unwrap_or([0x1f, 0x1f, 0x1f]),
+        }
+    }
+
+    /// create the palette slice for the creature's body colors
+    pub fn body_colors(&self) -> [u8; 12] {
+        color_ramp(self.body_color())
+    }
+
+    /// create the palette slice for the creature's secondary colors
+    pub fn secondary_colors(&self) -> [u8; 12] {
+        color_ramp(self.secondary_color())
+    }
+
+    /// Check that all parameters are within their valid ranges.
+    pub fn validate(&self) -> Result<(), CodeError> {
+        for kind in PartKind::ALL {
+            let value = self.part(kind);
+            if value >= kind.count() {
+                let param = kind.category().label;
+                return Err(CodeError::OutOfRange { param, value });
+            }
+        }
+        Ok(())
+    }
+
+    /// Turn the creature into a short code which can be shared.
+    ///
+    /// The code is made of a version byte,
+    /// the parts in [`CODE_PARTS`] order packed 2 per byte,
+    /// the custom color if there is one (3 bytes),
+    /// and a checksum byte, all written in base32.
+    /// Every category in the catalog must have at most 16 parts.
+    pub fn to_code(self) -> String {
+        // the parts up to the last one which is not 0
+        let parts_needed = CODE_PARTS
+            .iter()
+            .rposition(|kind| self.part(*kind) != 0)
+            .map_or(0, |i| i + 1);
+        let has_color = self.custom_color.is_some();
+        // (version 1 is only read, never written)
+        let (version, &(num_parts, _)) = CODE_VERSIONS
+            .iter()
+            .enumerate()
+            .skip(1)
+            .find(|(_, &(num_parts, color))| num_parts >= parts_needed && color == has_color)
+            .expect("no code version can hold this creature");
+
+        let mut bytes = [0u8; 16];
+        bytes[0] = version as u8 + 1;
+        for (i, kind) in CODE_PARTS.iter().take(num_parts).enumerate() {
+            let shift = if i % 2 == 0 { 4 } else { 0 };
+            bytes[1 + i / 2] |= self.part(*kind) << shift;
+        }
+        let mut len = code_bytes(num_parts) - 1;
+        if let Some(rgb) = self.custom_color {
+            bytes[len..len + CODE_COLOR_BYTES].copy_from_slice(&rgb);
+            len += CODE_COLOR_BYTES;
+        }
+        bytes[len] = crc8(&bytes[..len]);
+        len += 1;
+
+        let bits = bytes[..len]
+            .iter()
+            .fold(0u128, |acc, b| acc << 8 | *b as u128);
+        (0..code_chars(len))
+            .rev()
+            .map(|i| CODE_ALPHABET[(bits >> (i * 5)) as usize & 0x1f] as char)
+            .collect()
+    }
+
+    /// Decode a creature from a code made with [`to_code`](Self::to_code).
+    ///
+    /// Lowercase letters are accepted,
+    /// as well as `O`, `I` and `L` in place of `0` and `1`.
+    /// Codes from older versions are also accepted,
+    /// the parts which they do not have are left at 0.
+    pub fn from_code(code: &str) -> Result<Self, CodeError> {
+        let code = code.trim();
+        let len = code.chars().count();
+        if !CODE_LENGTHS.contains(&len) {
+            return Err(CodeError::BadLength(len));
+        }
+        let num_bytes = len * 5 / 8;
+
+        let mut bits = 0u128;
+        for c in code.chars() {
+            let c_norm = match c.to_ascii_uppercase() {
+                'O' => '0',
+                'I' | 'L' => '1',
+                c => c,
+            };
+            let value = CODE_ALPHABET
+                .iter()
+                .position(|&x| x as char == c_norm)
+                .ok_or(CodeError::BadCharacter(c))?;
+            bits = bits << 5 | value as u128;
+        }
+        let bytes = bits.to_be_bytes();
+        let (padding, bytes) = bytes.split_at(16 - num_bytes);
+
+        // (the spare bits at the start of the first character are always 0)
+        if padding.iter().any(|&b| b != 0) || crc8(&bytes[..num_bytes - 1]) != bytes[num_bytes - 1]
+        {
+            return Err(CodeError::BadChecksum);
+        }
+        let (num_parts, has_color) = (bytes[0] as usize)
+            .checked_sub(1)
+            .and_then(|i| CODE_VERSIONS.get(i))
+            .copied()
+            .ok_or(CodeError::UnsupportedVersion(bytes[0]))?;
+        let color_bytes = if has_color { CODE_COLOR_BYTES } else { 0 };
+        let parts_len = code_bytes(num_parts) - 1;
+        if parts_len + color_bytes + 1 != num_bytes {
+            return Err(CodeError::BadLength(len));
+        }
+
+        let mut creature = CreatureParams::default();
+        for (i, kind) in CODE_PARTS.iter().take(num_parts).enumerate() {
+            let shift = if i % 2 == 0 { 4 } else { 0 };
+            *creature.part_mut(*kind) = bytes[1 + i / 2] >> shift & 0xf;
+        }
+        if color_bytes > 0 {
+            let rgb = [bytes[parts_len], bytes[parts_len + 1], bytes[parts_len + 2]];
+            if let Some(&value) = rgb.iter().find(|&&c| c > 63) {
+                let param = "Custom color";
+                return Err(CodeError::OutOfRange { param, value });
+            }
+            creature.custom_color = Some(rgb);
+        }
+        creature.validate()?;
+        Ok(creature)
+    }
+
+    /// The creature's name as a DOS file name without extension:
+    /// up to 8 uppercase letters.
+    pub fn file_stem(&self) -> String {
+        use core::fmt::Write;
+        let mut name = String::new();
+        let _ = write!(name, "{self}");
+        name.chars()
+            .filter(char::is_ascii_alphabetic)
+            .take(8)
+            .map(|c| c.to_ascii_uppercase())
+            .collect()
+    }
+
+    /// Find all creatures which have the given name
+    /// (case insensitive, trailing `!` is ignored).
+    ///
+    /// The list is empty if no creature has this name.
+    /// Names are unique except for the colors:
+    /// the name only has the named color nearest to the body color,
+    /// and nothing of the secondary color.
+    /// So the creature found is the one-color creature
+    /// of that named color and no custom color,
+    /// and creatures which only differ in their colors share the same name.
+    /// More than one creature would mean that the name is ambiguous.
+    pub fn parse_name(name: &str) -> Vec<CreatureParams> {
+        let name = name.trim().trim_end_matches('!');
+        let shapes = PartKind::Shape.category().syllables;
+
+        // match each portion of the name in the same order as `Display`,
+        // keeping every creature which could have produced the name so far
+        let mut candidates = alloc::vec![(CreatureParams::default(), name)];
+        candidates = match_syllables(candidates, PartKind::Headwear);
+        candidates = expand(candidates, |creature, rest, out| {
+            for (shape, syllable) in shapes.iter().enumerate() {
+                let (prefix, _) = split_shape_syllable(syllable);
+                if let Some(rest) = strip_prefix_ignore_case(rest, prefix) {
+                    let shape = shape as u8;
+                    out.push((CreatureParams { shape, ..creature }, rest));
+                }
+            }
+        });
+        candidates = match_syllables(candidates, PartKind::Color);
+        candidates = expand(candidates, |creature, rest, out| {
+            let (_, suffix) = split_shape_syllable(shapes[creature.shape as usize]);
+            if let Some(rest) = strip_prefix_ignore_case(rest, suffix) {
+                out.push((creature, rest));
+            }
+        });
+        candidates = expand(candidates, |creature, rest, out| {
+            for (arms, row) in CATALOG.limb_syllables.iter().enumerate() {
+                for (legs, syllable) in row.iter().enumerate() {
+                    if let Some(rest) = strip_prefix_ignore_case(rest, syllable) {
+                        let mut creature = creature;
+                        creature.legs = legs as u8;
+                        creature.arms = arms as u8;
+                        out.push((creature, rest));
+                    }
+                }
+            }
+        });
+        for kind in [
+            PartKind::Eyes,
+            PartKind::Mouth,
+            PartKind::Tail,
+            PartKind::Pattern,
+        ] {
+            candidates = match_syllables(candidates, kind);
+        }
+
+        candidates
+            .into_iter()
+            .filter(|(_, rest)| rest.is_empty())
+            .map(|(creature, _)| creature)
+            .collect()
+    }
+}
+
+/// a partially parsed creature name:
+/// the creature so far and the rest of the name
+type Candidates<'a> = Vec<(CreatureParams, &'a str)>;
+
+/// continue each candidate in every way that `f` finds
+/// to match the start of the rest of its name
+fn expand<'a>(
+    candidates: Candidates<'a>,
+    f: impl Fn(CreatureParams, &'a str, &mut Candidates<'a>),
+) -> Candidates<'a> {
+    let mut out = Vec::new();
+    for (creature, rest) in candidates {
+        f(creature, rest, &mut out);
+    }
+    out
+}
+
+/// continue each candidate with the parts of the given category
+/// whose syllable is next in the name
+fn match_syllables(candidates: Candidates<'_>, kind: PartKind) -> Candidates<'_> {
+    expand(candidates, |creature, rest, out| {
+        for (part, syllable) in kind.category().syllables.iter().enumerate() {
+            if let Some(rest) = strip_prefix_ignore_case(rest, syllable) {
+                let mut creature = creature;
+                *creature.part_mut(kind) = part as u8;
+                out.push((creature, rest));
+            }
+        }
+    })
+}
+
+/// pick a random part of the given kind,
+/// weighted by the rarity of each part
+/// (see [`PartCategory::weight`](crate::catalog::PartCategory::weight))
+fn random_part(kind: PartKind, rng: &mut impl RandRange<u16>) -> u8 {
+    let category = kind.category();
+    let weights = (0..category.count).map(|part| category.weight(part));
+    let mut pick = rng.next_range(0..weights.clone().sum());
+    for (part, weight) in weights.enumerate() {
+        if pick < weight {
+            return part as u8;
+        }
+        pick -= weight;
+    }
+    category.count - 1
+}
+
+/// the named color whose hue is nearest to the given 6-bit RGB color,
+/// or white if the color is too grey to have a meaningful hue
+fn nearest_named_color(rgb: [u8; 3]) -> u8 {
+    // below this saturation, colors count as white
+    const MIN_SATURATION: u8 = 12;
+
+    let hsv = Hsv::from_rgb(rgb);
+    let named = CATALOG.body_colors.iter().map(|c| Hsv::from_rgb(*c));
+    if hsv.s < MIN_SATURATION {
+        // the least saturated named color
+        return named
+            .enumerate()
+            .min_by_key(|(_, named)| named.s)
+            .map_or(0, |(i, _)| i as u8);
+    }
+    named
+        .enumerate()
+        .filter(|(_, named)| named.s >= MIN_SATURATION)
+        .min_by_key(|(_, named)| hue_distance(named.h, hsv.h))
+        .map_or(0, |(i, _)| i as u8)
+}
+
+/// the palette slice for 4 shades of the given color
+fn color_ramp(base_color: [u8; 3]) -> [u8; 12] {
+    [
+        // light shade
+        (base_color[0] + 24).min(63),
+        (base_color[1] + 24).min(63),
+        (base_color[2] + 24).min(63),
+        // base color
+        base_color[0],
+        base_color[1],
+        base_color[2],
+        // darker shade
+        base_color[0] / 2,
+        base_color[1] / 2,
+        base_color[2] / 2,
+        // darker shade
+        base_color[0] / 4,
+        base_color[1] / 4,
+        base_color[2] / 4,
+    ]
+}
+
+/// the number of bytes in a creature code with the given number of parts
+/// (version, 2 parts per byte, checksum)
+const fn code_bytes(num_parts: usize) -> usize {
+    1 + num_parts.div_ceil(2) + 1
+}
+
+/// the number of base32 characters needed for the given number of bytes
+const fn code_chars(num_bytes: usize) -> usize {
+    (num_bytes * 8).div_ceil(5)
+}
+
+/// split a shape syllable into the portions
+/// which go before and after the color syllable
+fn split_shape_syllable(syllable: &str) -> (&str, &str) {
+    syllable.split_once('-').unwrap_or((syllable, ""))
+}
+
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = text.get(..prefix.len())?;
+    if head.eq_ignore_ascii_case(prefix) {
+        Some(&text[prefix.len()..])
+    } else {
+        None
+    }
+}
+
+/// CRC-8 (polynomial 0x07) for creature code checksums
+fn crc8(data: &[u8]) -> u8 {
+    let mut crc = 0u8;
+    for b in data {
+        crc ^= b;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                crc << 1 ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// The Display impl prints the creature's generated name
+impl core::fmt::Display for CreatureParams {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // portion defined by the creature's color
+        let display2 = PartKind::Color
+            .category()
+            .syllables
+            .get(self.color as usize)
+            .unwrap_or(&"Unknown");
+
+        // title defined by the creature's headwear
+        f.write_str(PartKind::Headwear.category().syllables[self.headwear as usize])?;
+
+        // part 1
+        let (prefix, suffix) =
+            split_shape_syllable(PartKind::Shape.category().syllables[self.shape as usize]);
+        write!(f, "{prefix}{display2}{suffix}")?;
+
+        // defined by creature's limbs
+        let display5 = CATALOG
+            .limb_syllables
+            .get(self.arms as usize)
+            .and_then(|row| row.get(self.legs as usize))
+            .unwrap_or(&"");
+        f.write_str(display5)?;
+
+        // defined by creature's eyes
+        f.write_str(PartKind::Eyes.category().syllables[self.eyes as usize])?;
+
+        // defined by creature's mouth
+        f.write_str(PartKind::Mouth.category().syllables[self.mouth as usize])?;
+
+        // defined by creature's tail
+        f.write_str(PartKind::Tail.category().syllables[self.tail as usize])?;
+
+        // defined by creature's body pattern
+        f.write_str(PartKind::Pattern.category().syllables[self.pattern as usize])
+    }
+}
+
+/// A short flavor text about a creature,
+/// with one sentence for each of the parts in `DESCRIBED_PARTS`
+/// (see [`PartCategory::lore`](crate::catalog::PartCategory::lore)).
+///
+/// The first sentence starts with the creature's name,
+/// as in "Flanin lives in sunny deserts. It eats mostly berries."
+#[derive(Debug, Copy, Clone)]
+pub struct Description<'a>(&'a CreatureParams);
+
+impl core::fmt::Display for Description<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let creature = self.0;
+        let mut first = true;
+        for kind in DESCRIBED_PARTS {
+            let Some(lore) = kind.category().lore.get(creature.part(kind) as usize) else {
+                continue;
+            };
+            if first {
+                write!(f, "{creature} {lore}.")?;
+                first = false;
+            } else {
+                write!(f, " It {lore}.")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::fmt::Write;
+
+    /// the parts which make up a creature's name
+    const NAMED_PARTS: [PartKind; 9] = [
+        PartKind::Headwear,
+        PartKind::Shape,
+        PartKind::Color,
+        PartKind::Legs,
+        PartKind::Arms,
+        PartKind::Eyes,
+        PartKind::Mouth,
+        PartKind::Tail,
+        PartKind::Pattern,
+    ];
+
+    /// every combination of the parts in `NAMED_PARTS`,
+    /// with the other parts left at 0
+    fn named_creatures() -> impl Iterator<Item = CreatureParams> {
+        let total: u64 = NAMED_PARTS.iter().map(|kind| kind.count() as u64).product();
+        (0..total).map(|mut i| {
+            let mut creature = CreatureParams::default();
+            for kind in NAMED_PARTS {
+                let count = kind.count() as u64;
+                *creature.part_mut(kind) = (i % count) as u8;
+                i /= count;
+            }
+            creature
+        })
+    }
+
+    #[test]
+    fn code_lengths_cover_every_version() {
+        for (num_parts, has_color) in CODE_VERSIONS {
+            let color_bytes = if has_color { CODE_COLOR_BYTES } else { 0 };
+            let len = code_chars(code_bytes(num_parts) + color_bytes);
+            assert!(CODE_LENGTHS.contains(&len), "code length {len}");
+        }
+        assert_eq!(CODE_LENGTHS.iter().max(), Some(&CODE_LEN));
+    }
+
+    #[test]
+    fn codes_have_room_for_every_part() {
+        // (parts are packed 2 per byte)
+        for kind in PartKind::ALL {
+            assert!(kind.count() <= 16, "{kind:?} has {} parts", kind.count());
+        }
+    }
+
+    #[test]
+    fn codes_round_trip() {
+        let mut rng = Xorshift::seed(0xc0de);
+        for _ in 0..10_000 {
+            let mut creature = CreatureParams::new_random(&mut rng);
+            let code = creature.to_code();
+            assert_eq!(
+                CreatureParams::from_code(&code),
+                Ok(creature),
+                "code {code}"
+            );
+
+            creature.set_custom_color(core::array::from_fn(|_| rng.next_range(0..64_u16) as u8));
+            let code = creature.to_code();
+            assert_eq!(
+                CreatureParams::from_code(&code),
+                Ok(creature),
+                "code {code}"
+            );
+        }
+    }
+
+    #[test]
+    fn codes_with_a_typo_are_rejected() {
+        let mut creature = CreatureParams::new_random(&mut Xorshift::seed(0xc0de));
+        creature.set_custom_color([40, 41, 10]);
+        for code in [CreatureParams::default().to_code(), creature.to_code()] {
+            for i in 0..code.len() {
+                for &c in CODE_ALPHABET {
+                    if code.as_bytes()[i] == c {
+                        continue;
+                    }
+                    let mut typo = code.clone().into_bytes();
+                    typo[i] = c;
+                    let typo = String::from_utf8(typo).unwrap();
+                    assert_eq!(
+                        CreatureParams::from_code(&typo),
+                        Err(CodeError::BadChecksum),
+                        "code {typo}, from {code}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn version_1_codes_still_decode() {
+        let creature = CreatureParams {
+            shape: 3,
+            color: 2,
+            eyes: 4,
+            mouth: 1,
+            legs: 5,
+            arms: 2,
+            ..CreatureParams::default()
+        };
+        assert_eq!(CreatureParams::from_code("04S42MPP"), Ok(creature));
+        assert_eq!(CreatureParams::from_code("04s42mpp"), Ok(creature));
+    }
+
+    #[test]
+    fn codes_of_other_lengths_are_rejected() {
+        let code = "04S42MPP";
+        assert!(CreatureParams::from_code(code).is_ok());
+        for len in [9, 10, 13, 14] {
+            let longer = alloc::format!("{code:0>len$}");
+            assert_eq!(
+                CreatureParams::from_code(&longer),
+                Err(CodeError::BadLength(len))
+            );
+        }
+    }
+
+    #[test]
+    fn names_leave_out_custom_and_secondary_colors() {
+        let mut creature = CreatureParams {
+            shape: 3,
+            color: 2,
+            secondary: 5,
+            ..CreatureParams::default()
+        };
+        creature.set_custom_color([40, 41, 10]);
+        let one_color = CreatureParams {
+            shape: 3,
+            color: creature.color,
+            ..CreatureParams::default()
+        };
+        let name = alloc::format!("{creature}");
+        assert_eq!(CreatureParams::parse_name(&name), [one_color]);
+    }
+
+    #[test]
+    fn every_name_is_unique() {
+        let mut name = String::new();
+        for creature in named_creatures() {
+            name.clear();
+            write!(name, "{creature}").unwrap();
+            let found = CreatureParams::parse_name(&name);
+            assert_eq!(found, [creature], "creatures named {name:?}");
+        }
+    }
+
+    #[test]
+    fn random_parts_follow_their_weights() {
+        const SAMPLES: u32 = 200_000;
+        let mut rng = Xorshift::seed(0x5eed);
+        for kind in PartKind::ALL {
+            let category = kind.category();
+            assert!(
+                category.weights.is_empty() || category.weights.len() == category.count as usize,
+                "{kind:?} has {} weights for {} parts",
+                category.weights.len(),
+                category.count,
+            );
+            let mut counts = [0_u32; 256];
+            for _ in 0..SAMPLES {
+                counts[random_part(kind, &mut rng) as usize] += 1;
+            }
+            let total: u32 = (0..category.count)
+                .map(|part| category.weight(part) as u32)
+                .sum();
+            for part in 0..category.count {
+                let expected = SAMPLES as f64 * category.weight(part) as f64 / total as f64;
+                let found = counts[part as usize] as f64;
+                assert!(
+                    (found - expected).abs() < expected * 0.1,
+                    "{kind:?} part {part} picked {found} times, expected about {expected}",
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn random_rarities_follow_the_documented_split() {
+        const SAMPLES: u32 = 200_000;
+        // as documented in `Rarity::of_score`
+        let split = [
+            (Rarity::Common, 0.56),
+            (Rarity::Uncommon, 0.33),
+            (Rarity::Rare, 0.09),
+            (Rarity::Legendary, 0.02),
+        ];
+        let mut rng = Xorshift::seed(0x5eed);
+        let mut counts = [0_u32; 4];
+        for _ in 0..SAMPLES {
+            counts[CreatureParams::new_random(&mut rng).rarity() as usize] += 1;
+        }
+        for ((rarity, share), count) in split.into_iter().zip(counts) {
+            let found = count as f64 / SAMPLES as f64;
+            assert!(
+                (found - share).abs() < 0.01,
+                "{rarity:?} creatures are {found:.3} of random ones, expected about {share}",
+            );
+        }
+    }
+}
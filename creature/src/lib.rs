@@ -0,0 +1,14 @@
+//! The creatures of Create-a-Creature:
+//! the catalog of their parts, their colors, their stats,
+//! and how they are named, coded and bred
+//!
+//! This crate does not depend on anything DOS,
+//! so that it can be tested on the host
+//! (run `cargo test` in this directory).
+#![no_std]
+extern crate alloc;
+
+pub mod catalog;
+pub mod color;
+pub mod creature;
+pub mod stats;
@@ -0,0 +1,114 @@
+//! Creature stats, derived from the creature's parts
+//!
+//! Every stat starts at [`BASE_STAT`],
+//! then each part adds its bonuses from the catalog
+//! (see [`PartCategory::stat_bonuses`](crate::catalog::PartCategory::stat_bonuses)).
+//! Tune those tables to make parts better or worse at something.
+
+use crate::catalog::PartKind;
+use crate::creature::CreatureParams;
+
+pub const NUM_STATS: usize = 5;
+
+/// the value of every stat before any part bonuses
+pub const BASE_STAT: u8 = 3;
+
+/// the highest value of a stat
+pub const MAX_STAT: u8 = 10;
+
+/// One of the stats of a creature
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[repr(u8)]
+pub enum Stat {
+    Speed,
+    Strength,
+    Smarts,
+    Charm,
+    Stamina,
+}
+
+impl Stat {
+    /// all stats, in the order shown in the editor
+    pub const ALL: [Stat; NUM_STATS] = [
+        Stat::Speed,
+        Stat::Strength,
+        Stat::Smarts,
+        Stat::Charm,
+        Stat::Stamina,
+    ];
+
+    /// the name of the stat, as shown in the game
+    pub fn name(self) -> &'static str {
+        match self {
+            Stat::Speed => "Speed",
+            Stat::Strength => "Strength",
+            Stat::Smarts => "Smarts",
+            Stat::Charm => "Charm",
+            Stat::Stamina => "Stamina",
+        }
+    }
+}
+
+/// The stats of a creature, indexed by [`Stat`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Stats(pub [u8; NUM_STATS]);
+
+impl Stats {
+    /// the stats of the given creature
+    pub fn of(creature: &CreatureParams) -> Self {
+        let mut stats = [BASE_STAT as i16; NUM_STATS];
+        for kind in PartKind::ALL {
+            let bonuses = kind.category().stat_bonuses;
+            if let Some(bonuses) = bonuses.get(creature.part(kind) as usize) {
+                for (stat, bonus) in stats.iter_mut().zip(bonuses) {
+                    *stat += *bonus as i16;
+                }
+            }
+        }
+        Stats(stats.map(|stat| stat.clamp(0, MAX_STAT as i16) as u8))
+    }
+
+    /// the value of one stat, from 0 to `MAX_STAT`
+    pub fn get(&self, stat: Stat) -> u8 {
+        self.0[stat as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_part_has_stat_bonuses() {
+        for kind in PartKind::ALL {
+            let bonuses = kind.category().stat_bonuses;
+            assert!(
+                bonuses.is_empty() || bonuses.len() == kind.count() as usize,
+                "{kind:?} has stat bonuses for {} of its {} parts",
+                bonuses.len(),
+                kind.count(),
+            );
+        }
+    }
+
+    #[test]
+    fn stats_stay_in_range() {
+        // every combination of the parts with stat bonuses
+        let kinds: alloc::vec::Vec<_> = PartKind::ALL
+            .into_iter()
+            .filter(|kind| !kind.category().stat_bonuses.is_empty())
+            .collect();
+        let total: u32 = kinds.iter().map(|kind| kind.count() as u32).product();
+        for mut i in 0..total {
+            let mut creature = CreatureParams::default();
+            for &kind in &kinds {
+                *creature.part_mut(kind) = (i % kind.count() as u32) as u8;
+                i /= kind.count() as u32;
+            }
+            let stats = Stats::of(&creature);
+            for stat in Stat::ALL {
+                assert!(stats.get(stat) <= MAX_STAT, "{creature:?} has {stats:?}");
+            }
+        }
+    }
+}
@@ -9,11 +9,21 @@ use dos_x::{
 };
 use opbinary::vgm::OplCommand;
 
+use crate::config::key_bindings;
+
 static mut NO_SOUND: bool = false;
 static mut NO_MUSIC: bool = false;
 
 static MUSIC_VGM: &[u8] = include_bytes!("../resources/createac.vgm");
 
+/// the highest music volume (the default)
+pub const MAX_VOLUME: u8 = 10;
+
+/// the first OPL register with the key scale and total level of an operator
+const REG_LEVEL: u8 = 0x40;
+/// the number of operator slots in each OPL register range
+const NUM_SLOTS: usize = 0x16;
+
 // Hz
 const PIT_FREQUENCY: u32 = 0x1234DD;
 
@@ -31,6 +41,36 @@ pub fn music_off() {
     }
 }
 
+/// whether sound effects are enabled
+pub fn is_sound_on() -> bool {
+    unsafe { !NO_SOUND }
+}
+
+/// whether music is enabled
+pub fn is_music_on() -> bool {
+    unsafe { !NO_MUSIC }
+}
+
+/// Turn sound effects on or off.
+pub fn set_sound(on: bool) {
+    unsafe {
+        NO_SOUND = !on;
+    }
+}
+
+/// Turn music on or off while the game is running.
+///
+/// The music is paused, silencing the notes being played,
+/// and resumes from the same point once turned back on.
+pub fn set_music(on: bool) {
+    unsafe {
+        NO_MUSIC = !on;
+    }
+    if !on {
+        adlib_notes_off();
+    }
+}
+
 /// Play a very short click sound
 pub fn play_click_1() {
     play_click_impl(1800, 2);
@@ -81,6 +121,34 @@ unsafe fn pc_speaker_off() {
     }
 }
 
+/// Handle the hotkeys for turning sound on and off,
+/// available in every scene of the game
+/// (M toggles music and X toggles sound effects, unless bound to other keys).
+pub fn sound_hotkeys(key: u8) {
+    static mut KEYSTATE_MUSIC: bool = false;
+    static mut KEYSTATE_SOUND: bool = false;
+    let keys = key_bindings();
+    unsafe {
+        if key == keys.music {
+            if !KEYSTATE_MUSIC {
+                KEYSTATE_MUSIC = true;
+                set_music(!is_music_on());
+            }
+        } else if key == keys.music | 0x80 {
+            KEYSTATE_MUSIC = false;
+        } else if key == keys.effects {
+            if !KEYSTATE_SOUND {
+                KEYSTATE_SOUND = true;
+                set_sound(!is_sound_on());
+                // (a click to tell that it is on)
+                play_click_1();
+            }
+        } else if key == keys.effects | 0x80 {
+            KEYSTATE_SOUND = false;
+        }
+    }
+}
+
 #[inline]
 pub fn adlib_notes_off() {
     unsafe {
@@ -104,6 +172,11 @@ pub struct AdlibPlayer {
     vgm: Option<opbinary::vgm::OplVgm>,
     cmd_index: core::cell::Cell<usize>,
     timer: core::cell::Cell<u32>,
+    /// the music volume, from 0 to `MAX_VOLUME`
+    volume: Cell<u8>,
+    /// the levels of each operator as given by the music,
+    /// before applying the volume
+    levels: Cell<[u8; NUM_SLOTS]>,
 }
 
 fn samples_to_us(samples: u32) -> u32 {
@@ -112,15 +185,19 @@ fn samples_to_us(samples: u32) -> u32 {
 }
 
 impl AdlibPlayer {
-    pub fn load(vgm_data: &[u8]) -> Self {
-        if unsafe { NO_MUSIC } {
-            return AdlibPlayer {
-                vgm: None,
-                cmd_index: Cell::new(0),
-                timer: Cell::new(0),
-            };
+    /// A player which never plays anything,
+    /// for when there is no Adlib sound card
+    pub fn silent() -> Self {
+        AdlibPlayer {
+            vgm: None,
+            cmd_index: Cell::new(0),
+            timer: Cell::new(0),
+            volume: Cell::new(MAX_VOLUME),
+            levels: Cell::new([0; NUM_SLOTS]),
         }
+    }
 
+    pub fn load(vgm_data: &[u8]) -> Self {
         let vgm =
             opbinary::vgm::Vgm::from_bytes(vgm_data).expect("Could not read embedded VGM data");
 
@@ -128,13 +205,50 @@ impl AdlibPlayer {
             vgm: Some(vgm.into_opl_vgm()),
             cmd_index: Cell::new(0),
             timer: Cell::new(0),
+            volume: Cell::new(MAX_VOLUME),
+            levels: Cell::new([0; NUM_SLOTS]),
+        }
+    }
+
+    /// the music volume, from 0 to `MAX_VOLUME`
+    pub fn volume(&self) -> u8 {
+        self.volume.get()
+    }
+
+    /// Change the music volume (from 0 to `MAX_VOLUME`),
+    /// also for the notes being played.
+    pub fn set_volume(&self, volume: u8) {
+        self.volume.set(volume.min(MAX_VOLUME));
+        if self.vgm.is_none() {
+            return;
+        }
+        for (slot, level) in self.levels.get().into_iter().enumerate() {
+            if is_carrier(slot) {
+                unsafe {
+                    adlib::write_command_l(REG_LEVEL + slot as u8, self.scale_level(level));
+                }
+            }
         }
     }
 
+    /// attenuate an operator level (as in the OPL level registers)
+    /// according to the volume
+    fn scale_level(&self, level: u8) -> u8 {
+        let volume = self.volume.get() as u16;
+        let total_level = (level & 0x3f) as u16;
+        let total_level =
+            total_level + (0x3f - total_level) * (MAX_VOLUME as u16 - volume) / MAX_VOLUME as u16;
+        (level & 0xc0) | total_level as u8
+    }
+
     pub fn poll(&self, delta_microseconds: u32) -> PlaybackState {
         let Some(vgm) = &self.vgm else {
             return PlaybackState::Stopped;
         };
+        if unsafe { NO_MUSIC } {
+            // paused, carry on from here when music is back on
+            return PlaybackState::Stopped;
+        }
 
         let timer = self.timer.get().saturating_sub(delta_microseconds);
         self.timer.set(timer);
@@ -148,7 +262,18 @@ impl AdlibPlayer {
                     address,
                     data,
                 } => unsafe {
-                    adlib::write_command_l(*address, *data);
+                    let slot = address.wrapping_sub(REG_LEVEL) as usize;
+                    if slot < NUM_SLOTS {
+                        // keep the level to apply volume changes later
+                        let mut levels = self.levels.get();
+                        levels[slot] = *data;
+                        self.levels.set(levels);
+                    }
+                    if slot < NUM_SLOTS && is_carrier(slot) {
+                        adlib::write_command_l(*address, self.scale_level(*data));
+                    } else {
+                        adlib::write_command_l(*address, *data);
+                    }
                 },
                 OplCommand::Opl3 {
                     port: 1,
@@ -190,11 +315,24 @@ impl AdlibPlayer {
     }
 }
 
+/// whether the operator slot is a carrier,
+/// which sets the loudness of its channel
+/// (the others are modulators, which set its timbre)
+fn is_carrier(slot: usize) -> bool {
+    matches!(slot % 8, 3..=5)
+}
+
 /// Initialize the Adlib music player,
-/// loading the game music if music is enabled.
+/// loading the game music if there is an Adlib sound card.
 ///
-/// If music is disable, the returned dummy player does nothing.
-pub fn load_player() -> AdlibPlayer {
+/// Without one, the returned dummy player does nothing.
+/// The music is loaded even if it is disabled,
+/// so that it can be turned on later.
+pub fn load_player(adlib_detected: bool) -> AdlibPlayer {
+    if !adlib_detected {
+        return AdlibPlayer::silent();
+    }
+
     // load OPL data of music
     let mut player = AdlibPlayer::load(MUSIC_VGM);
 
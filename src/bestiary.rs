@@ -0,0 +1,187 @@
+//! The bestiary, a log of every creature made so far
+
+use alloc::vec::Vec;
+use alloc::{format, string::String};
+use dos_x::{
+    key,
+    vga::{clear_screen, draw_rect, vsync},
+};
+
+use crate::{
+    audio::play_click_1,
+    catalog::PartKind,
+    creature::CreatureParams,
+    file::{read_file, replace_file},
+    gfx::{COLOR_BACKGROUND, COLOR_BLACK, COLOR_HIGHLIGHT, COLOR_WHITE},
+    global_hotkeys, wait_for_enter_release, Assets,
+};
+
+/// The file where the bestiary is kept
+/// (one creature code per line)
+const BESTIARY_FILE: &core::ffi::CStr = c"BESTIARY.TXT";
+/// where the bestiary goes while it is being written
+const BESTIARY_TEMP_FILE: &core::ffi::CStr = c"BESTIARY.TMP";
+
+/// The record of all creatures made by the player
+#[derive(Debug, Default)]
+pub struct Bestiary {
+    /// every distinct creature logged so far
+    /// (custom colors are not kept)
+    creatures: Vec<CreatureParams>,
+}
+
+impl Bestiary {
+    /// Load the bestiary from disk.
+    ///
+    /// Starts empty if there is no bestiary file yet.
+    /// Lines which are not valid creature codes are skipped.
+    pub fn load() -> Self {
+        let mut bestiary = Bestiary::default();
+        let Some(data) = read_file(BESTIARY_FILE) else {
+            return bestiary;
+        };
+        for line in data.split(|&b| b == b'\n') {
+            let Ok(code) = core::str::from_utf8(line) else {
+                continue;
+            };
+            if let Ok(creature) = CreatureParams::from_code(code) {
+                bestiary.log(&creature);
+            }
+        }
+        bestiary
+    }
+
+    /// Save the bestiary to disk,
+    /// returning whether it was successful.
+    pub fn save(&self) -> bool {
+        let mut data = String::new();
+        for creature in &self.creatures {
+            data.push_str(&creature.to_code());
+            data.push_str("\r\n");
+        }
+        replace_file(BESTIARY_FILE, BESTIARY_TEMP_FILE, data.as_bytes())
+    }
+
+    /// Record a creature in the bestiary,
+    /// returning whether it was not there before.
+    pub fn log(&mut self, creature: &CreatureParams) -> bool {
+        let creature = CreatureParams {
+            custom_color: None,
+            ..*creature
+        };
+        if self.creatures.contains(&creature) {
+            return false;
+        }
+        self.creatures.push(creature);
+        true
+    }
+
+    /// the number of distinct creatures logged
+    pub fn num_creatures(&self) -> usize {
+        self.creatures.len()
+    }
+
+    /// whether any logged creature has the given part
+    pub fn is_part_used(&self, kind: PartKind, part: u8) -> bool {
+        self.creatures.iter().any(|c| c.part(kind) == part)
+    }
+
+    /// the number of parts of the given category
+    /// which appear in at least one logged creature
+    pub fn parts_used(&self, kind: PartKind) -> u8 {
+        (0..kind.count())
+            .filter(|&part| self.is_part_used(kind, part))
+            .count() as u8
+    }
+}
+
+/// Show the bestiary screen until the player leaves it.
+pub fn bestiary_screen(assets: &Assets, bestiary: &Bestiary) {
+    let Assets {
+        adlib_player,
+        big_font,
+        small_font,
+        ..
+    } = assets;
+
+    unsafe {
+        vsync();
+        clear_screen(COLOR_BACKGROUND);
+    }
+
+    big_font.draw_text(91, 7, "Bestiary", COLOR_WHITE);
+    big_font.draw_text(92, 8, "Bestiary", COLOR_BLACK);
+
+    let text = format!("Creatures found: {}", bestiary.num_creatures());
+    small_font.draw_text((320 - text.len() as i32 * 9) / 2, 30, text, COLOR_BLACK);
+
+    const BASE_Y: i32 = 46;
+    const STRIDE: i32 = 13;
+    // where the parts are drawn, one small square each
+    const PARTS_X: i32 = 200;
+    const PART_STRIDE: i32 = 8;
+
+    let mut total_used = 0;
+    let mut total = 0;
+    for (i, kind) in PartKind::ALL.into_iter().enumerate() {
+        let y = BASE_Y + STRIDE * i as i32;
+        let used = bestiary.parts_used(kind);
+        total_used += used as u32;
+        total += kind.count() as u32;
+
+        small_font.draw_text(8, y, kind.category().label, COLOR_BLACK);
+        small_font.draw_text(152, y, format!("{used}/{}", kind.count()), COLOR_BLACK);
+
+        // parts not used yet are highlighted
+        for part in 0..kind.count() {
+            let color = if bestiary.is_part_used(kind, part) {
+                COLOR_BLACK
+            } else {
+                COLOR_HIGHLIGHT
+            };
+            unsafe {
+                draw_rect(PARTS_X + PART_STRIDE * part as i32, y + 1, 6, 6, color);
+            }
+        }
+    }
+
+    let y = BASE_Y + STRIDE * PartKind::ALL.len() as i32 + 6;
+    small_font.draw_text(8, y, format!("Overall: {total_used}/{total}"), COLOR_BLACK);
+    unsafe {
+        draw_rect(PARTS_X, y + 1, 6, 6, COLOR_HIGHLIGHT);
+    }
+    small_font.draw_text(PARTS_X + 10, y, "not used", COLOR_BLACK);
+
+    let mut keystate_enter = false;
+
+    wait_for_enter_release(assets);
+    loop {
+        unsafe {
+            vsync();
+        }
+
+        let key = key::get_keypress();
+        global_hotkeys(key);
+        match key {
+            // enter
+            0x1c => {
+                keystate_enter = true;
+            }
+            // enter release
+            0x9c => {
+                if keystate_enter {
+                    play_click_1();
+                    return;
+                }
+            }
+            // escape release
+            0x81 => {
+                play_click_1();
+                return;
+            }
+            _ => {}
+        }
+
+        adlib_player.poll(14_400);
+    }
+}
@@ -0,0 +1,280 @@
+//! The creature breeding mode
+
+use alloc::format;
+use dos_x::{
+    key,
+    vga::{clear_screen, draw_rect, vsync, Palette},
+};
+use tinyrand::RandRange;
+
+use crate::{
+    audio::{play_click_1, play_click_2},
+    create::main_game,
+    creature::CreatureParams,
+    gfx::{
+        draw_arrow_left, draw_arrow_right, set_creature_palette, PaletteSlots, COLOR_BACKGROUND,
+        COLOR_BLACK, COLOR_WHITE,
+    },
+    global_hotkeys, wait_for_enter_release, Assets,
+};
+
+/// the mutation chances which can be picked (1 in N for each part),
+/// 0 meaning no mutations
+const MUTATION_ODDS: [u16; 6] = [0, 50, 20, 10, 5, 2];
+
+/// the mutation chance picked at first (1 in 10)
+const DEFAULT_MUTATION: usize = 3;
+
+/// What the game should do as breeding ends
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BreedOutcome {
+    /// Present the offspring of the two parents
+    Offspring(CreatureParams),
+    /// Return to the main menu
+    Back,
+}
+
+/// Pick two parent creatures and breed them.
+pub fn breeding(
+    assets: &Assets,
+    parents: &mut [CreatureParams; 2],
+    palette: &mut Palette,
+    rng: &mut impl RandRange<u16>,
+) -> BreedOutcome {
+    let Assets {
+        adlib_player,
+        creature_assets,
+        big_font,
+        small_font,
+        ..
+    } = assets;
+
+    // where each parent is drawn
+    const PARENT_X: [i32; 2] = [64, 224];
+    const PARENT_Y: i32 = 44;
+
+    const BASE_Y: i32 = 100;
+    const STRIDE: i32 = 14;
+    const TEXT_X: i32 = 88;
+    const LABELS: [&str; 4] = [
+        "Edit parent 1",
+        "Random parent 1",
+        "Edit parent 2",
+        "Random parent 2",
+    ];
+    // the row for the mutation chance
+    const MUTATION: usize = 4;
+    // the last row is for the Breed! action
+    const BREED: usize = 5;
+
+    // each parent has its own colors
+    let mut slots = PaletteSlots::new();
+    let parent_slots = [
+        slots.alloc(palette, &parents[0]),
+        slots.alloc(palette, &parents[1]),
+    ];
+
+    let draw_parent = |parents: &[CreatureParams; 2], i: usize, palette: &mut Palette| {
+        if let Some(slot) = parent_slots[i] {
+            slot.set_colors(palette, &parents[i]);
+        }
+        unsafe {
+            draw_rect(PARENT_X[i], PARENT_Y, 32, 32, COLOR_BACKGROUND);
+        }
+        creature_assets.draw_creature_in_slot(&parents[i], PARENT_X[i], PARENT_Y, parent_slots[i]);
+    };
+
+    let draw_mutation = |mutation: usize| {
+        let text = match MUTATION_ODDS[mutation] {
+            0 => "Mutation: never".into(),
+            odds => format!("Mutation: 1 in {odds}"),
+        };
+        let y = BASE_Y + STRIDE * MUTATION as i32;
+        unsafe {
+            draw_rect(TEXT_X, y, 153, 8, COLOR_BACKGROUND);
+        }
+        small_font.draw_text(TEXT_X, y, text, COLOR_BLACK);
+    };
+
+    let draw_screen = |parents: &[CreatureParams; 2], mutation: usize, palette: &mut Palette| {
+        unsafe {
+            vsync();
+            clear_screen(COLOR_BACKGROUND);
+        }
+
+        big_font.draw_text(91, 11, "Breeding", COLOR_WHITE);
+        big_font.draw_text(92, 12, "Breeding", COLOR_BLACK);
+
+        for i in 0..2 {
+            draw_parent(parents, i, palette);
+        }
+        small_font.draw_text(PARENT_X[0] - 20, PARENT_Y + 38, "Parent 1", COLOR_BLACK);
+        small_font.draw_text(PARENT_X[1] - 20, PARENT_Y + 38, "Parent 2", COLOR_BLACK);
+
+        for (i, label) in LABELS.iter().enumerate() {
+            small_font.draw_text(TEXT_X, BASE_Y + STRIDE * i as i32, label, COLOR_BLACK);
+        }
+        draw_mutation(mutation);
+        small_font.draw_text(
+            TEXT_X,
+            BASE_Y + STRIDE * BREED as i32 + 8,
+            "Breed!",
+            COLOR_BLACK,
+        );
+    };
+
+    let mut mutation = DEFAULT_MUTATION;
+    draw_screen(parents, mutation, palette);
+
+    // selector for different actions:
+    // edit or randomize each parent, the mutation chance, then Breed!
+    let mut selector = 0;
+
+    let mut keystate_up = false;
+    let mut keystate_down = false;
+    let mut keystate_left = false;
+    let mut keystate_right = false;
+    let mut keystate_enter = false;
+    // (ESC leaves the editor on release, which should not leave this screen too)
+    let mut keystate_escape = false;
+
+    wait_for_enter_release(assets);
+    loop {
+        unsafe {
+            vsync();
+        }
+
+        const ARROW_LEFT: u32 = 76;
+        const ARROW_RIGHT: u32 = 248;
+        // clear regions with selection arrow
+        unsafe {
+            let height = STRIDE as u32 * BREED as u32 + 16;
+            draw_rect(ARROW_LEFT as i32, BASE_Y, 7, height, COLOR_BACKGROUND);
+            draw_rect(ARROW_RIGHT as i32, BASE_Y, 7, height, COLOR_BACKGROUND);
+        }
+        let selection_y = BASE_Y as u32 + selector as u32 * STRIDE as u32;
+        let selection_y = if selector == BREED {
+            selection_y + 8
+        } else {
+            selection_y
+        };
+        draw_arrow_left(ARROW_LEFT, selection_y, COLOR_BLACK);
+        draw_arrow_right(ARROW_RIGHT, selection_y, COLOR_BLACK);
+
+        let key = key::get_keypress();
+        global_hotkeys(key);
+        match key {
+            // up
+            0x48 | 0x11 => {
+                if !keystate_up {
+                    keystate_up = true;
+                    if selector > 0 {
+                        selector -= 1;
+                        play_click_1();
+                    }
+                }
+            }
+            // up release
+            0xc8 | 0x91 => {
+                keystate_up = false;
+            }
+            // down
+            0x50 | 0x1f => {
+                if !keystate_down {
+                    keystate_down = true;
+                    if selector < BREED {
+                        selector += 1;
+                        play_click_1();
+                    }
+                }
+            }
+            // down release
+            0xd0 | 0x9f => {
+                keystate_down = false;
+            }
+
+            // left: lower mutation chance
+            0x4b | 0x1e => {
+                if !keystate_left {
+                    keystate_left = true;
+                    if selector == MUTATION && mutation > 0 {
+                        mutation -= 1;
+                        draw_mutation(mutation);
+                        play_click_2();
+                    }
+                }
+            }
+            // left release
+            0xcb | 0x9e => {
+                keystate_left = false;
+            }
+
+            // right: higher mutation chance
+            0x4d | 0x20 => {
+                if !keystate_right {
+                    keystate_right = true;
+                    if selector == MUTATION && mutation < MUTATION_ODDS.len() - 1 {
+                        mutation += 1;
+                        draw_mutation(mutation);
+                        play_click_2();
+                    }
+                }
+            }
+            // right release
+            0xcd | 0xa0 => {
+                keystate_right = false;
+            }
+
+            // enter
+            0x1c => {
+                if !keystate_enter {
+                    keystate_enter = true;
+                    match selector {
+                        0 | 2 => {
+                            // design the parent in the editor
+                            play_click_2();
+                            let parent = &mut parents[selector / 2];
+                            set_creature_palette(palette, parent);
+                            // (leaving the editor with ESC also comes back here)
+                            main_game(assets, parent, palette);
+                            draw_screen(parents, mutation, palette);
+                        }
+                        1 | 3 => {
+                            play_click_2();
+                            parents[selector / 2] = CreatureParams::new_random(rng);
+                            draw_parent(parents, selector / 2, palette);
+                        }
+                        BREED => {
+                            play_click_2();
+                            let [mother, father] = parents;
+                            let offspring = mother.breed(father, MUTATION_ODDS[mutation], rng);
+                            set_creature_palette(palette, &offspring);
+                            return BreedOutcome::Offspring(offspring);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            // enter release
+            0x9c => {
+                keystate_enter = false;
+            }
+
+            // escape
+            0x01 => {
+                keystate_escape = true;
+            }
+            // escape release to go back to the main menu
+            0x81 => {
+                if keystate_escape {
+                    play_click_1();
+                    return BreedOutcome::Back;
+                }
+            }
+
+            _ => {}
+        }
+
+        adlib_player.poll(14_400);
+    }
+}
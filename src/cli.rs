@@ -0,0 +1,233 @@
+//! Command line arguments
+//!
+//! Options start with `/` or `-` (or `--`)
+//! and are case insensitive, as usual in DOS.
+//! See [`USAGE`] for the options available.
+
+use alloc::ffi::CString;
+use alloc::format;
+use dos_x::println;
+
+use crate::{creature::CreatureParams, export::ExportFormat};
+
+/// the largest scale of exported images
+const MAX_SCALE: u32 = 8;
+
+/// the help message of the program
+const USAGE: &str = "\
+Usage: CREATEAC [options]
+       CREATEAC EXPORT (/CREATURE=code | /NAME=name) [/SCALE=n] [file]
+
+Options:
+  /NOSOUND        Turn off music and sound effects
+  /NOMUSIC        Turn off music
+  /NOSFX          Turn off sound effects
+  /SEED=n         Seed of random creatures (a number)
+  /CREATURE=code  Start with the creature of this code
+  /NAME=name      Start with the creature of this name
+  /?              Show this help
+
+EXPORT writes a picture of the creature (file.PCX)
+or an animation of it jumping (file.GIF) and exits.";
+
+/// What the program was asked to do
+#[derive(Debug)]
+pub enum Command {
+    /// play the game
+    Play(PlayOptions),
+    /// export a creature to an image file, without starting the game
+    Export {
+        creature: CreatureParams,
+        path: CString,
+        format: ExportFormat,
+        scale: u32,
+    },
+    /// show the help message and exit
+    Help,
+}
+
+/// Options for playing the game
+#[derive(Debug, Default)]
+pub struct PlayOptions {
+    /// the creature to start with
+    pub creature: Option<CreatureParams>,
+    /// whether to turn off the music
+    pub no_music: bool,
+    /// whether to turn off sound effects
+    pub no_sfx: bool,
+    /// the seed of the random number generator
+    pub seed: Option<u64>,
+}
+
+/// Print the help message of the program.
+pub fn print_usage() {
+    println!("{USAGE}");
+}
+
+/// Parse the command line arguments (without the program name).
+///
+/// Returns `None` if they cannot be used,
+/// after printing what is wrong with them.
+pub fn parse_args(args: &[&[u8]]) -> Option<Command> {
+    match args {
+        [command, rest @ ..] if command.eq_ignore_ascii_case(b"export") => parse_export(rest),
+        _ => parse_play(args),
+    }
+}
+
+fn parse_play(args: &[&[u8]]) -> Option<Command> {
+    let mut options = PlayOptions::default();
+    for arg in args {
+        // (options without a prefix are also accepted,
+        // as in earlier versions of the game)
+        let (name, value) = split_option(arg).unwrap_or((arg, None));
+        match (name, value) {
+            _ if is_help(name) => return Some(Command::Help),
+            (name, None) if name.eq_ignore_ascii_case(b"nosound") => {
+                options.no_music = true;
+                options.no_sfx = true;
+            }
+            (name, None) if name.eq_ignore_ascii_case(b"nomusic") => {
+                options.no_music = true;
+            }
+            (name, None) if name.eq_ignore_ascii_case(b"nosfx") => {
+                options.no_sfx = true;
+            }
+            (name, Some(value)) if name.eq_ignore_ascii_case(b"seed") => {
+                let value = core::str::from_utf8(value).unwrap_or_default();
+                let Ok(seed) = value.parse() else {
+                    println!("Invalid seed {value:?}, it must be a number");
+                    return None;
+                };
+                options.seed = Some(seed);
+            }
+            (name, Some(value)) => match parse_creature(name, value) {
+                Some(creature) => options.creature = Some(creature?),
+                None => return unknown_option(arg),
+            },
+            _ => return unknown_option(arg),
+        }
+    }
+    Some(Command::Play(options))
+}
+
+fn parse_export(args: &[&[u8]]) -> Option<Command> {
+    let mut creature = None;
+    let mut file_name = None;
+    let mut scale = 1;
+    for arg in args {
+        let Some((name, value)) = split_option(arg) else {
+            // not an option, so it must be the file name
+            if file_name.is_some() {
+                let arg = core::str::from_utf8(arg).unwrap_or_default();
+                println!("Unexpected argument {arg:?}");
+                return None;
+            }
+            file_name = Some(*arg);
+            continue;
+        };
+        match (name, value) {
+            _ if is_help(name) => return Some(Command::Help),
+            (name, Some(value)) if name.eq_ignore_ascii_case(b"scale") => {
+                let value = core::str::from_utf8(value).unwrap_or_default();
+                match value.parse() {
+                    Ok(s @ 1..=MAX_SCALE) => scale = s,
+                    _ => {
+                        println!("Invalid scale {value:?}, it must be from 1 to {MAX_SCALE}");
+                        return None;
+                    }
+                }
+            }
+            (name, Some(value)) => match parse_creature(name, value) {
+                Some(c) => creature = Some(c?),
+                None => return unknown_option(arg),
+            },
+            _ => return unknown_option(arg),
+        }
+    }
+
+    let Some(creature) = creature else {
+        println!("Which creature? Give it as /CREATURE=code or /NAME=name");
+        return None;
+    };
+
+    // named after the creature by default
+    let file_name = match file_name {
+        Some(file_name) => file_name.into(),
+        None => format!("{}.PCX", creature.file_stem()).into_bytes(),
+    };
+    let Some(format) = ExportFormat::from_path(&file_name) else {
+        println!("Can only export to .PCX or .GIF files");
+        return None;
+    };
+    let path = CString::new(file_name).ok()?;
+    Some(Command::Export {
+        creature,
+        path,
+        format,
+        scale,
+    })
+}
+
+/// split an option into its name and value (after `=`),
+/// or `None` if it is not an option
+/// (`name=value` without a prefix counts as one)
+fn split_option(arg: &[u8]) -> Option<(&[u8], Option<&[u8]>)> {
+    let prefixed = arg
+        .strip_prefix(b"--")
+        .or_else(|| arg.strip_prefix(b"-"))
+        .or_else(|| arg.strip_prefix(b"/"));
+    let option = prefixed.unwrap_or(arg);
+    match option.iter().position(|&b| b == b'=') {
+        Some(i) => Some((&option[..i], Some(&option[i + 1..]))),
+        None => prefixed.map(|name| (name, None)),
+    }
+}
+
+/// whether the option asks for help
+fn is_help(name: &[u8]) -> bool {
+    name == b"?" || name.eq_ignore_ascii_case(b"help") || name.eq_ignore_ascii_case(b"h")
+}
+
+/// report an option which is not recognized
+fn unknown_option(arg: &[u8]) -> Option<Command> {
+    let arg = core::str::from_utf8(arg).unwrap_or_default();
+    println!("Unknown option {arg:?}\n");
+    print_usage();
+    None
+}
+
+/// parse a `CREATURE=code` or `NAME=name` option,
+/// returning `None` if it is neither,
+/// or `Some(None)` if it does not give a single creature
+/// (after printing why)
+fn parse_creature(name: &[u8], value: &[u8]) -> Option<Option<CreatureParams>> {
+    let value = core::str::from_utf8(value).unwrap_or_default();
+    if name.eq_ignore_ascii_case(b"creature") {
+        return match CreatureParams::from_code(value) {
+            Ok(c) => Some(Some(c)),
+            Err(e) => {
+                println!("Invalid creature code {value:?}: {e}");
+                Some(None)
+            }
+        };
+    }
+    if !name.eq_ignore_ascii_case(b"name") {
+        return None;
+    }
+
+    match CreatureParams::parse_name(value).as_slice() {
+        [] => {
+            println!("There is no creature named {value:?}");
+            Some(None)
+        }
+        [c] => Some(Some(*c)),
+        candidates => {
+            println!("The name {value:?} is ambiguous, it could be any of these:");
+            for c in candidates {
+                println!("    /CREATURE={}", c.to_code());
+            }
+            Some(None)
+        }
+    }
+}
@@ -0,0 +1,338 @@
+//! The game options, kept in the INI-style file `CREATEAC.CFG`
+//!
+//! ```ini
+//! [sound]
+//! music = on
+//! effects = on
+//! volume = 10
+//!
+//! [keys]
+//! screenshot = F12
+//! music = M
+//! effects = X
+//!
+//! [game]
+//! creature = 00G00000002J
+//! background = Baby blue
+//! ```
+//!
+//! Every entry is optional.
+//! Lines which cannot be understood are reported and skipped,
+//! and so are keys bound to more than one hotkey.
+//! Command line switches take precedence over the file.
+
+use alloc::format;
+use alloc::string::String;
+use core::ffi::CStr;
+use dos_x::println;
+
+use crate::{
+    audio::MAX_VOLUME,
+    creature::CreatureParams,
+    file::{read_file, replace_file},
+    gfx::BACKGROUND_COLORS,
+};
+
+const CONFIG_FILE: &CStr = c"CREATEAC.CFG";
+/// where the config file goes while it is being written
+const CONFIG_TEMP_FILE: &CStr = c"CREATCFG.TMP";
+
+/// The keys of the hotkeys available in every scene,
+/// as keyboard scancodes
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct KeyBindings {
+    /// take a screenshot
+    pub screenshot: u8,
+    /// turn music on or off
+    pub music: u8,
+    /// turn sound effects on or off
+    pub effects: u8,
+}
+
+impl KeyBindings {
+    pub const DEFAULT: KeyBindings = KeyBindings {
+        screenshot: 0x58,
+        music: 0x32,
+        effects: 0x2d,
+    };
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+static mut KEY_BINDINGS: KeyBindings = KeyBindings::DEFAULT;
+
+/// the keys currently bound to the hotkeys
+pub fn key_bindings() -> KeyBindings {
+    unsafe { KEY_BINDINGS }
+}
+
+/// Bind the hotkeys to other keys.
+pub fn set_key_bindings(bindings: KeyBindings) {
+    unsafe {
+        KEY_BINDINGS = bindings;
+    }
+}
+
+/// the keys which can be named in the config file, by name
+/// (those in `RESERVED_KEYS` cannot be bound to hotkeys though)
+static KEY_NAMES: [(&str, u8); 51] = [
+    ("1", 0x02),
+    ("2", 0x03),
+    ("3", 0x04),
+    ("4", 0x05),
+    ("5", 0x06),
+    ("6", 0x07),
+    ("7", 0x08),
+    ("8", 0x09),
+    ("9", 0x0a),
+    ("0", 0x0b),
+    ("Backspace", 0x0e),
+    ("Tab", 0x0f),
+    ("Q", 0x10),
+    ("W", 0x11),
+    ("E", 0x12),
+    ("R", 0x13),
+    ("T", 0x14),
+    ("Y", 0x15),
+    ("U", 0x16),
+    ("I", 0x17),
+    ("O", 0x18),
+    ("P", 0x19),
+    ("A", 0x1e),
+    ("S", 0x1f),
+    ("D", 0x20),
+    ("F", 0x21),
+    ("G", 0x22),
+    ("H", 0x23),
+    ("J", 0x24),
+    ("K", 0x25),
+    ("L", 0x26),
+    ("Z", 0x2c),
+    ("X", 0x2d),
+    ("C", 0x2e),
+    ("V", 0x2f),
+    ("B", 0x30),
+    ("N", 0x31),
+    ("M", 0x32),
+    ("Space", 0x39),
+    ("F1", 0x3b),
+    ("F2", 0x3c),
+    ("F3", 0x3d),
+    ("F4", 0x3e),
+    ("F5", 0x3f),
+    ("F6", 0x40),
+    ("F7", 0x41),
+    ("F8", 0x42),
+    ("F9", 0x43),
+    ("F10", 0x44),
+    ("F11", 0x57),
+    ("F12", 0x58),
+];
+
+/// the keys which the scenes of the game already use,
+/// so that they cannot be bound to hotkeys:
+/// W, A, S and D to move around, P and G to export, D to describe,
+/// E to edit, Y and N to answer, and 1 to 4 for the export size
+static RESERVED_KEYS: [u8; 13] = [
+    0x11, 0x1e, 0x1f, 0x20, 0x19, 0x22, 0x12, 0x15, 0x31, 0x02, 0x03, 0x04, 0x05,
+];
+
+/// whether a key can be bound to hotkeys
+pub fn is_bindable(scancode: u8) -> bool {
+    key_name(scancode).is_some() && !RESERVED_KEYS.contains(&scancode)
+}
+
+/// the name of a key by its scancode,
+/// or `None` if it has no name here
+pub fn key_name(scancode: u8) -> Option<&'static str> {
+    KEY_NAMES
+        .iter()
+        .find(|(_, code)| *code == scancode)
+        .map(|(name, _)| *name)
+}
+
+/// the scancode of a key by its name (case insensitive)
+fn key_by_name(name: &str) -> Option<u8> {
+    KEY_NAMES
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, code)| *code)
+}
+
+/// The game options
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Config {
+    /// whether music is on
+    pub music: bool,
+    /// whether sound effects are on
+    pub effects: bool,
+    /// the music volume, from 0 to `MAX_VOLUME`
+    pub volume: u8,
+    pub keys: KeyBindings,
+    /// the creature to start with
+    /// (instead of a random one)
+    pub creature: Option<CreatureParams>,
+    /// the background color, as an index in `BACKGROUND_COLORS`
+    pub background: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            music: true,
+            effects: true,
+            volume: MAX_VOLUME,
+            keys: KeyBindings::DEFAULT,
+            creature: None,
+            background: 0,
+        }
+    }
+}
+
+impl Config {
+    /// Load the options from the config file,
+    /// or the default options if there is no such file.
+    /// The file is only ever written by the options screen.
+    ///
+    /// Lines which cannot be understood are reported in text mode
+    /// and skipped,
+    /// and the default keys are used
+    /// if the same key is bound to more than one hotkey.
+    /// Also returns whether anything was reported.
+    pub fn load() -> (Self, bool) {
+        let mut config = Config::default();
+        let Some(data) = read_file(CONFIG_FILE) else {
+            return (config, false);
+        };
+
+        let mut reported = false;
+        let mut section = String::new();
+        for (i, line) in data.split(|&b| b == b'\n').enumerate() {
+            if let Err(e) = config.load_line(&mut section, line) {
+                println!("CREATEAC.CFG line {}: {e}, skipped", i + 1);
+                reported = true;
+            }
+        }
+
+        let KeyBindings {
+            screenshot,
+            music,
+            effects,
+        } = config.keys;
+        if screenshot == music || screenshot == effects || music == effects {
+            println!("CREATEAC.CFG: the same key is bound to more than one hotkey, using the default keys");
+            config.keys = KeyBindings::DEFAULT;
+            reported = true;
+        }
+        (config, reported)
+    }
+
+    /// read one line of the config file,
+    /// keeping track of the section it is in
+    fn load_line(&mut self, section: &mut String, line: &[u8]) -> Result<(), String> {
+        let line = core::str::from_utf8(line).map_err(|_| "not readable text")?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+            return Ok(());
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name.strip_suffix(']').ok_or("missing `]`")?;
+            *section = name.trim().to_ascii_lowercase();
+            return Ok(());
+        }
+
+        let (key, value) = line.split_once('=').ok_or("expected `key = value`")?;
+        self.set(section, key.trim(), value.trim())
+    }
+
+    /// set one option from the config file
+    fn set(&mut self, section: &str, key: &str, value: &str) -> Result<(), String> {
+        let key = key.to_ascii_lowercase();
+        match (section, key.as_str()) {
+            ("sound", "music") => self.music = parse_switch(value)?,
+            ("sound", "effects") => self.effects = parse_switch(value)?,
+            ("sound", "volume") => {
+                self.volume = match value.parse() {
+                    Ok(v @ 0..=MAX_VOLUME) => v,
+                    _ => return Err(format!("volume must be from 0 to {MAX_VOLUME}")),
+                }
+            }
+            ("keys", "screenshot") => self.keys.screenshot = parse_key(value)?,
+            ("keys", "music") => self.keys.music = parse_key(value)?,
+            ("keys", "effects") => self.keys.effects = parse_key(value)?,
+            ("game", "creature") => {
+                self.creature = if value.is_empty() {
+                    None
+                } else {
+                    let creature = CreatureParams::from_code(value)
+                        .map_err(|e| format!("invalid creature code {value:?} ({e})"))?;
+                    Some(creature)
+                }
+            }
+            ("game", "background") => {
+                self.background = BACKGROUND_COLORS
+                    .iter()
+                    .position(|(name, _)| name.eq_ignore_ascii_case(value))
+                    .ok_or_else(|| format!("unknown background color {value:?}"))?;
+            }
+            _ => return Err(format!("unknown option `{key}` in [{section}]")),
+        }
+        Ok(())
+    }
+
+    /// Write the options to the config file,
+    /// returning whether it was successful.
+    pub fn save(&self) -> bool {
+        let switch = |on: bool| if on { "on" } else { "off" };
+        let creature = self.creature.map(|c| c.to_code()).unwrap_or_default();
+        let text = format!(
+            "; Create-a-Creature options\r\n\
+             \r\n\
+             [sound]\r\n\
+             music = {}\r\n\
+             effects = {}\r\n\
+             volume = {}\r\n\
+             \r\n\
+             [keys]\r\n\
+             screenshot = {}\r\n\
+             music = {}\r\n\
+             effects = {}\r\n\
+             \r\n\
+             [game]\r\n\
+             creature = {}\r\n\
+             background = {}\r\n",
+            switch(self.music),
+            switch(self.effects),
+            self.volume,
+            key_name(self.keys.screenshot).unwrap_or_default(),
+            key_name(self.keys.music).unwrap_or_default(),
+            key_name(self.keys.effects).unwrap_or_default(),
+            creature,
+            BACKGROUND_COLORS[self.background].0,
+        );
+        replace_file(CONFIG_FILE, CONFIG_TEMP_FILE, text.as_bytes())
+    }
+}
+
+/// parse an on/off option
+fn parse_switch(value: &str) -> Result<bool, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "on" | "yes" | "true" | "1" => Ok(true),
+        "off" | "no" | "false" | "0" => Ok(false),
+        _ => Err(format!("expected on or off, not {value:?}")),
+    }
+}
+
+/// parse the name of a key which can be bound to hotkeys
+fn parse_key(value: &str) -> Result<u8, String> {
+    let key = key_by_name(value).ok_or_else(|| format!("unknown key {value:?}"))?;
+    if !is_bindable(key) {
+        return Err(format!("key {value:?} is already used by the game"));
+    }
+    Ok(key)
+}
@@ -7,8 +7,15 @@ use dos_x::{
 
 use crate::{
     audio::{play_click_1, play_click_2},
+    catalog::{PartKind, NUM_PART_KINDS},
     creature::CreatureParams,
-    gfx::{draw_arrow_left, draw_arrow_right, set_creature_palette, COLOR_BACKGROUND, COLOR_BLACK},
+    gfx::{
+        draw_arrow_left, draw_arrow_right, set_creature_palette, COLOR_BACKGROUND, COLOR_BLACK,
+        COLOR_HIGHLIGHT, COLOR_WHITE,
+    },
+    global_hotkeys,
+    picker::color_picker,
+    stats::{Stat, Stats, MAX_STAT},
     Assets,
 };
 
@@ -28,14 +35,6 @@ pub fn main_game(
     creature: &mut CreatureParams,
     palette: &mut Palette,
 ) -> MainGameOutcome {
-    unsafe {
-        vsync();
-    }
-
-    unsafe {
-        clear_screen(253);
-    }
-
     let Assets {
         adlib_player,
         creature_assets,
@@ -43,32 +42,73 @@ pub fn main_game(
         ..
     } = assets;
 
-    let creature_x = (320 - 32) / 2;
+    // the creature is centered in the space right of the labels
+    let creature_x = 240;
     let creature_y = (200 - 32) / 2;
 
-    // draw the creature in the center of the screen
-    creature_assets.draw_creature(creature, creature_x, creature_y);
+    const BASE_Y: i32 = 16;
+
+    const STRIDE: i32 = 16;
 
-    const BASE_Y: i32 = 28;
+    // the last row is for the Done! action
+    const DONE: usize = NUM_PART_KINDS;
 
-    let stride = 24;
+    // the creature's stats are shown under it,
+    // one bar for each stat
+    const STATS_X: i32 = 198;
+    const STATS_Y: i32 = 128;
+    const STATS_STRIDE: i32 = 12;
+    const BAR_X: i32 = 272;
+    // the width of a bar for each point of a stat
+    const BAR_STEP: u32 = 4;
 
-    // draw the UI
-    small_font.draw_text(48, BASE_Y, "Shape", COLOR_BLACK);
-    small_font.draw_text(48, BASE_Y + stride, "Color", COLOR_BLACK);
-    small_font.draw_text(48, BASE_Y + stride * 2, "Eyes", COLOR_BLACK);
-    small_font.draw_text(48, BASE_Y + stride * 3, "Mouth", COLOR_BLACK);
-    small_font.draw_text(48, BASE_Y + stride * 4, "Legs", COLOR_BLACK);
-    small_font.draw_text(48, BASE_Y + stride * 5, "Arms", COLOR_BLACK);
-    small_font.draw_text(48, BASE_Y + stride * 6 + 8, "Done!", COLOR_BLACK);
+    let draw_stats = |creature: &CreatureParams| {
+        let stats = Stats::of(creature);
+        for (i, &stat) in Stat::ALL.iter().enumerate() {
+            let y = STATS_Y + STATS_STRIDE * i as i32 + 1;
+            let value = stats.get(stat) as u32;
+            unsafe {
+                if value > 0 {
+                    draw_rect(BAR_X, y, value * BAR_STEP, 6, COLOR_HIGHLIGHT);
+                }
+                if value < MAX_STAT as u32 {
+                    let x = BAR_X + (value * BAR_STEP) as i32;
+                    draw_rect(x, y, (MAX_STAT as u32 - value) * BAR_STEP, 6, COLOR_WHITE);
+                }
+            }
+        }
+    };
+
+    let draw_screen = |creature: &CreatureParams| {
+        unsafe {
+            vsync();
+        }
+
+        unsafe {
+            clear_screen(253);
+        }
+
+        // draw the creature
+        creature_assets.draw_creature(creature, creature_x, creature_y);
+
+        // draw the UI
+        for (i, kind) in PartKind::ALL.iter().enumerate() {
+            let label = kind.category().label;
+            small_font.draw_text(48, BASE_Y + STRIDE * i as i32, label, COLOR_BLACK);
+        }
+        small_font.draw_text(48, BASE_Y + STRIDE * DONE as i32 + 8, "Done!", COLOR_BLACK);
+
+        for (i, stat) in Stat::ALL.iter().enumerate() {
+            let y = STATS_Y + STATS_STRIDE * i as i32;
+            small_font.draw_text(STATS_X, y, stat.name(), COLOR_BLACK);
+        }
+        draw_stats(creature);
+    };
+    draw_screen(creature);
 
     // selector for different parameters/actions:
-    // 0: param0 (shape)
-    // 1: param1 (color)
-    // 2: param2 (eye type)
-    // 3: param3 (limbs)
-    // 4: param4 (mouth)
-    // 5: Done!
+    // one row for each part category (see `PartKind::ALL`),
+    // then Done!
     let mut selector = 0;
 
     let mut keystate_up = false;
@@ -83,14 +123,15 @@ pub fn main_game(
         }
 
         const ARROW_LEFT: u32 = 28;
-        const ARROW_RIGHT: u32 = 124;
+        const ARROW_RIGHT: u32 = 188;
         // clear regions with selection arrow
         unsafe {
-            dos_x::vga::draw_rect(ARROW_LEFT as i32, BASE_Y, 7, 160, COLOR_BACKGROUND);
-            dos_x::vga::draw_rect(ARROW_RIGHT as i32, BASE_Y, 7, 160, COLOR_BACKGROUND);
+            let height = STRIDE as u32 * DONE as u32 + 16;
+            dos_x::vga::draw_rect(ARROW_LEFT as i32, BASE_Y, 7, height, COLOR_BACKGROUND);
+            dos_x::vga::draw_rect(ARROW_RIGHT as i32, BASE_Y, 7, height, COLOR_BACKGROUND);
         }
-        let selection_y = BASE_Y as u32 + selector as u32 * 24;
-        let selection_y = if selector == 6 {
+        let selection_y = BASE_Y as u32 + selector as u32 * STRIDE as u32;
+        let selection_y = if selector == DONE {
             selection_y + 9
         } else {
             selection_y
@@ -102,6 +143,7 @@ pub fn main_game(
         // (W, A, S, D also works)
         let mut params_changed = false;
         let key = key::get_keypress();
+        global_hotkeys(key);
         match key {
             // up
             0x48 | 0x11 => {
@@ -122,7 +164,7 @@ pub fn main_game(
             0x50 | 0x1f => {
                 if !keystate_down {
                     keystate_down = true;
-                    if selector < 6 {
+                    if selector < DONE {
                         // move selection down
                         selector += 1;
                         play_click_1();
@@ -138,68 +180,11 @@ pub fn main_game(
             0x4b | 0x1e => {
                 if !keystate_left {
                     keystate_left = true;
-                    match selector {
-                        0 => {
-                            // change shape (rotate backwards)
-                            if creature.shape == 0 {
-                                creature.shape = (crate::creature::NUM_SHAPES as u8) - 1;
-                            } else {
-                                creature.shape -= 1;
-                            }
-                            params_changed = true;
-                            play_click_2();
-                        }
-                        1 => {
-                            // change color
-                            if creature.color == 0 {
-                                creature.color = (crate::creature::NUM_COLORS as u8) - 1;
-                            } else {
-                                creature.color -= 1;
-                            }
-                            params_changed = true;
-                            play_click_2();
-                        }
-                        2 => {
-                            // change eye type
-                            if creature.eyes == 0 {
-                                creature.eyes = (crate::creature::NUM_EYES as u8) - 1;
-                            } else {
-                                creature.eyes -= 1;
-                            }
-                            params_changed = true;
-                            play_click_2();
-                        }
-                        3 => {
-                            // change mouth
-                            if creature.mouth == 0 {
-                                creature.mouth = (crate::creature::NUM_MOUTHS as u8) - 1;
-                            } else {
-                                creature.mouth -= 1;
-                            }
-                            params_changed = true;
-                            play_click_2();
-                        }
-                        4 => {
-                            // change legs
-                            if creature.legs == 0 {
-                                creature.legs = (crate::creature::NUM_LEGS as u8) - 1;
-                            } else {
-                                creature.legs -= 1;
-                            }
-                            params_changed = true;
-                            play_click_2();
-                        }
-                        5 => {
-                            // change arms
-                            if creature.arms == 0 {
-                                creature.arms = (crate::creature::NUM_ARMS as u8) - 1;
-                            } else {
-                                creature.arms -= 1;
-                            }
-                            params_changed = true;
-                            play_click_2();
-                        }
-                        _ => {}
+                    if let Some(&kind) = PartKind::ALL.get(selector) {
+                        // change part (rotate backwards)
+                        creature.cycle_part(kind, true);
+                        params_changed = true;
+                        play_click_2();
                     }
                 }
             }
@@ -212,44 +197,11 @@ pub fn main_game(
             0x4d | 0x20 => {
                 if !keystate_right {
                     keystate_right = true;
-                    match selector {
-                        0 => {
-                            // change shape
-                            creature.shape = (creature.shape + 1) % crate::creature::NUM_SHAPES;
-                            params_changed = true;
-                            play_click_2();
-                        }
-                        1 => {
-                            // change color
-                            creature.color = (creature.color + 1) % crate::creature::NUM_COLORS;
-                            params_changed = true;
-                            play_click_2();
-                        }
-                        2 => {
-                            // change eye type
-                            creature.eyes = (creature.eyes + 1) % crate::creature::NUM_EYES;
-                            params_changed = true;
-                            play_click_2();
-                        }
-                        3 => {
-                            // change mouth
-                            creature.mouth = (creature.mouth + 1) % crate::creature::NUM_MOUTHS;
-                            params_changed = true;
-                            play_click_2();
-                        }
-                        4 => {
-                            // change legs
-                            creature.legs = (creature.legs + 1) % crate::creature::NUM_LEGS;
-                            params_changed = true;
-                            play_click_2();
-                        }
-                        5 => {
-                            // change arms
-                            creature.arms = (creature.arms + 1) % crate::creature::NUM_ARMS;
-                            params_changed = true;
-                            play_click_2();
-                        }
-                        _ => {}
+                    if let Some(&kind) = PartKind::ALL.get(selector) {
+                        // change part
+                        creature.cycle_part(kind, false);
+                        params_changed = true;
+                        play_click_2();
                     }
                 }
             }
@@ -260,10 +212,15 @@ pub fn main_game(
 
             // enter
             0x1c => {
-                if selector == 6 {
+                if selector == DONE {
                     // done!
                     play_click_2();
                     return MainGameOutcome::SaveCreature;
+                } else if PartKind::ALL[selector] == PartKind::Color {
+                    // pick a custom color
+                    play_click_2();
+                    color_picker(assets, creature, palette);
+                    draw_screen(creature);
                 }
             }
 
@@ -285,6 +242,7 @@ pub fn main_game(
             }
             // redraw the creature with new parameters
             creature_assets.draw_creature(creature, creature_x, creature_y);
+            draw_stats(creature);
         }
 
         adlib_player.poll(14_400);
@@ -0,0 +1,155 @@
+//! Exporting creatures to image files for sharing
+
+use alloc::ffi::CString;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ffi::CStr;
+use dos_x::vga::Palette;
+
+use crate::{
+    creature::CreatureParams,
+    file::write_file,
+    gfx::{encode_pcx, CreatureAssets, COLOR_BACKGROUND},
+    gif::GifEncoder,
+    Jump,
+};
+
+/// the size of each frame of the animated GIF,
+/// enough for the creature at any height of its jump
+const GIF_WIDTH: u16 = 40;
+const GIF_HEIGHT: u16 = 48;
+/// the animation keeps one in this many frames
+/// of the presentation screen
+const GIF_FRAME_STEP: u32 = 2;
+/// how long each frame of the GIF is shown, in hundredths of a second
+/// (about two frames at 70 Hz)
+const GIF_FRAME_DELAY: u16 = 3;
+
+/// The kinds of image files which creatures can be exported to
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ExportFormat {
+    /// a still PCX image
+    Pcx,
+    /// an animated GIF image of the creature jumping
+    Gif,
+}
+
+impl ExportFormat {
+    /// the format for the extension of the given file name
+    /// (case insensitive), if it is supported
+    pub fn from_path(path: &[u8]) -> Option<Self> {
+        let dot = path.iter().rposition(|&b| b == b'.')?;
+        let extension = &path[dot + 1..];
+        if extension.eq_ignore_ascii_case(b"PCX") {
+            Some(ExportFormat::Pcx)
+        } else if extension.eq_ignore_ascii_case(b"GIF") {
+            Some(ExportFormat::Gif)
+        } else {
+            None
+        }
+    }
+
+    /// the usual file extension of the format
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Pcx => "PCX",
+            ExportFormat::Gif => "GIF",
+        }
+    }
+
+    /// Encode the creature as an image file in this format,
+    /// scaled up by an integer factor.
+    pub fn encode(
+        self,
+        creature_assets: &CreatureAssets,
+        creature: &CreatureParams,
+        palette: &Palette,
+        scale: u32,
+    ) -> Vec<u8> {
+        match self {
+            ExportFormat::Pcx => encode_creature_pcx(creature_assets, creature, palette, scale),
+            ExportFormat::Gif => encode_creature_gif(creature_assets, creature, palette, scale),
+        }
+    }
+}
+
+/// Export the creature as an image named after it,
+/// scaled up by an integer factor.
+///
+/// Returns the file name if successful.
+pub fn export_creature(
+    creature_assets: &CreatureAssets,
+    creature: &CreatureParams,
+    palette: &Palette,
+    format: ExportFormat,
+    scale: u32,
+) -> Option<String> {
+    let file_name = format!("{}.{}", creature.file_stem(), format.extension());
+    let path = CString::new(file_name.as_str()).ok()?;
+    export_creature_to(creature_assets, creature, palette, format, scale, &path)
+        .then_some(file_name)
+}
+
+/// Export the creature as an image to the given file,
+/// scaled up by an integer factor.
+///
+/// Returns whether the file was written.
+pub fn export_creature_to(
+    creature_assets: &CreatureAssets,
+    creature: &CreatureParams,
+    palette: &Palette,
+    format: ExportFormat,
+    scale: u32,
+    path: &CStr,
+) -> bool {
+    let data = format.encode(creature_assets, creature, palette, scale);
+    write_file(path, &data)
+}
+
+/// the creature as a PCX image
+fn encode_creature_pcx(
+    creature_assets: &CreatureAssets,
+    creature: &CreatureParams,
+    palette: &Palette,
+    scale: u32,
+) -> Vec<u8> {
+    let mut render = [COLOR_BACKGROUND; 32 * 32];
+    creature_assets.render_creature(creature, &mut render, None);
+    encode_pcx(&render, 32, 32, scale, &palette.0)
+}
+
+/// one loop of the creature jumping as an animated GIF image
+fn encode_creature_gif(
+    creature_assets: &CreatureAssets,
+    creature: &CreatureParams,
+    palette: &Palette,
+    scale: u32,
+) -> Vec<u8> {
+    let mut render = [COLOR_BACKGROUND; 32 * 32];
+    creature_assets.render_creature(creature, &mut render, None);
+
+    let (width, height) = (GIF_WIDTH as usize, GIF_HEIGHT as usize);
+    let mut gif = GifEncoder::new(GIF_WIDTH, GIF_HEIGHT, scale as u16, &palette.0);
+    let mut frame = vec![COLOR_BACKGROUND; width * height];
+
+    // start right as the creature lands,
+    // so that the last frame leads back to the first one
+    let mut jump = Jump::landed();
+    for i in 0..Jump::LOOP_FRAMES {
+        if i % GIF_FRAME_STEP == 0 {
+            frame.fill(COLOR_BACKGROUND);
+            // (the creature is at most 3 pixels above its resting height)
+            let y = (jump.y + 4) as usize;
+            for (row, pixels) in render.chunks(32).enumerate() {
+                let start = (y + row) * width + (width - 32) / 2;
+                frame[start..start + 32].copy_from_slice(pixels);
+            }
+            gif.add_frame(&frame, GIF_FRAME_DELAY);
+        }
+        jump.step();
+    }
+
+    gif.finish()
+}
@@ -0,0 +1,80 @@
+//! Reading and writing whole files through the DJGPP C library
+
+use alloc::vec::Vec;
+use core::ffi::CStr;
+use dos_x::djgpp::stdio::{fclose, fopen, fread, fwrite, remove, rename};
+
+/// Read the entire contents of a file,
+/// or `None` if the file cannot be opened.
+pub fn read_file(path: &CStr) -> Option<Vec<u8>> {
+    let mut data = Vec::new();
+    unsafe {
+        let file = fopen(path.as_ptr(), c"rb".as_ptr());
+        if file.is_null() {
+            return None;
+        }
+        let mut chunk = [0u8; 512];
+        loop {
+            let n = fread(chunk.as_mut_ptr().cast(), 1, chunk.len() as _, file) as usize;
+            data.extend_from_slice(&chunk[..n]);
+            if n < chunk.len() {
+                break;
+            }
+        }
+        fclose(file);
+    }
+    Some(data)
+}
+
+/// Check whether a file exists (and can be read).
+pub fn file_exists(path: &CStr) -> bool {
+    unsafe {
+        let file = fopen(path.as_ptr(), c"rb".as_ptr());
+        if file.is_null() {
+            return false;
+        }
+        fclose(file);
+        true
+    }
+}
+
+/// Write the given data to a file,
+/// replacing its previous contents.
+///
+/// Returns whether all of the data was written.
+pub fn write_file(path: &CStr, data: &[u8]) -> bool {
+    unsafe {
+        let file = fopen(path.as_ptr(), c"wb".as_ptr());
+        if file.is_null() {
+            return false;
+        }
+        let n = fwrite(data.as_ptr().cast(), 1, data.len() as _, file) as usize;
+        let closed = fclose(file) == 0;
+        n == data.len() && closed
+    }
+}
+
+/// Write the given data to a file by way of a temporary file,
+/// so that the previous contents are kept if writing fails.
+///
+/// Returns whether the file was replaced.
+pub fn replace_file(path: &CStr, temp_path: &CStr, data: &[u8]) -> bool {
+    if !write_file(temp_path, data) {
+        unsafe {
+            remove(temp_path.as_ptr());
+        }
+        return false;
+    }
+    rename_file(temp_path, path)
+}
+
+/// Rename a file, replacing the destination file if it exists.
+///
+/// Returns whether the file was renamed.
+pub fn rename_file(from: &CStr, to: &CStr) -> bool {
+    unsafe {
+        // (DOS does not rename over an existing file)
+        remove(to.as_ptr());
+        rename(from.as_ptr(), to.as_ptr()) == 0
+    }
+}
@@ -0,0 +1,324 @@
+//! The gallery of saved creatures
+
+use alloc::format;
+use dos_x::{
+    key,
+    vga::{blit_rect, clear_screen, draw_rect, vsync, Palette},
+};
+
+use crate::{
+    audio::{play_click_1, play_click_2},
+    check_saved,
+    creature::CreatureParams,
+    gfx::{
+        set_creature_palette, PaletteSlots, COLOR_BACKGROUND, COLOR_BLACK, COLOR_HIGHLIGHT,
+        COLOR_WHITE,
+    },
+    global_hotkeys, present_creature,
+    save::SaveFile,
+    wait_for_enter_release, Assets, Origin,
+};
+
+/// the number of columns of creatures in each page
+const COLUMNS: usize = 5;
+/// the number of rows of creatures in each page
+const ROWS: usize = 3;
+/// the number of creatures in each page
+const PAGE_SIZE: usize = COLUMNS * ROWS;
+
+const CELL_WIDTH: i32 = 64;
+const CELL_HEIGHT: i32 = 46;
+const GRID_Y: i32 = 28;
+
+/// What the game should do as the gallery is left
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GalleryOutcome {
+    /// Return to the main menu
+    Back,
+    /// Open this creature in the editor
+    Edit(CreatureParams),
+}
+
+/// Browse the creatures in the collection.
+pub fn gallery(assets: &Assets, save_file: &mut SaveFile, palette: &mut Palette) -> GalleryOutcome {
+    let Assets {
+        adlib_player,
+        creature_assets,
+        big_font,
+        small_font,
+        ..
+    } = assets;
+
+    // the position of the cell of a creature in the page
+    let cell_position = |i: usize| {
+        let x = (i % COLUMNS) as i32 * CELL_WIDTH;
+        let y = GRID_Y + (i / COLUMNS) as i32 * CELL_HEIGHT;
+        (x, y)
+    };
+
+    // each creature in the page has its own colors
+    let mut slots = PaletteSlots::new();
+
+    let mut draw_page = |save_file: &SaveFile, page: usize, palette: &mut Palette| {
+        slots.clear();
+        unsafe {
+            vsync();
+            clear_screen(COLOR_BACKGROUND);
+        }
+        big_font.draw_text(100, 5, "Gallery", COLOR_WHITE);
+        big_font.draw_text(101, 6, "Gallery", COLOR_BLACK);
+
+        if save_file.creatures.is_empty() {
+            small_font.draw_text(61, 96, "No creatures saved yet", COLOR_BLACK);
+            return;
+        }
+
+        let num_pages = save_file.creatures.len().div_ceil(PAGE_SIZE);
+        let text = format!("{}/{}", page + 1, num_pages);
+        small_font.draw_text(316 - text.len() as i32 * 9, 10, text, COLOR_BLACK);
+
+        let creatures = save_file.creatures.iter().skip(page * PAGE_SIZE);
+        for (i, saved) in creatures.take(PAGE_SIZE).enumerate() {
+            let (x, y) = cell_position(i);
+
+            let slot = slots.alloc(palette, &saved.creature);
+            let mut buffer = [COLOR_BACKGROUND; 32 * 32];
+            creature_assets.render_creature(&saved.creature, &mut buffer, slot);
+            unsafe {
+                blit_rect(&buffer, (32, 32), (0, 0, 32, 32), (x + 16, y + 2));
+            }
+
+            // the name, shortened to fit in the cell
+            let mut name = format!("{}", saved.creature);
+            if name.len() > 7 {
+                name.truncate(6);
+                name.push('.');
+            }
+            let name_x = x + (CELL_WIDTH - name.len() as i32 * 9) / 2;
+            small_font.draw_text(name_x, y + 37, name, COLOR_BLACK);
+        }
+
+        small_font.draw_text(34, 189, "ENTER:show E:edit DEL:delete", COLOR_BLACK);
+    };
+
+    // the full name and date of the selected creature
+    let draw_details = |save_file: &SaveFile, index: usize| {
+        unsafe {
+            draw_rect(0, 166, 320, 20, COLOR_BACKGROUND);
+        }
+        let Some(saved) = save_file.creatures.get(index) else {
+            return;
+        };
+        let name = format!("{}", saved.creature);
+        small_font.draw_text(
+            (320 - name.len() as i32 * 9) / 2,
+            166,
+            name,
+            COLOR_HIGHLIGHT,
+        );
+        let date = format!("Saved on {}", saved.saved_at);
+        small_font.draw_text((320 - date.len() as i32 * 9) / 2, 177, date, COLOR_BLACK);
+    };
+
+    // the selected creature in the whole collection
+    let mut selected = 0;
+    let mut page = 0;
+    draw_page(save_file, page, palette);
+    draw_details(save_file, selected);
+
+    let mut keystate_up = false;
+    let mut keystate_down = false;
+    let mut keystate_left = false;
+    let mut keystate_right = false;
+    let mut keystate_page = false;
+    let mut keystate_enter = false;
+    let mut keystate_edit = false;
+    let mut keystate_delete = false;
+    // whether the player is being asked to confirm deleting a creature
+    let mut confirm_delete = false;
+
+    wait_for_enter_release(assets);
+    loop {
+        unsafe {
+            vsync();
+        }
+
+        // draw a frame around the selected creature
+        for i in 0..PAGE_SIZE {
+            let (x, y) = cell_position(i);
+            let color = if page * PAGE_SIZE + i == selected && !save_file.creatures.is_empty() {
+                COLOR_HIGHLIGHT
+            } else {
+                COLOR_BACKGROUND
+            };
+            unsafe {
+                draw_rect(x + 14, y, 36, 1, color);
+                draw_rect(x + 14, y + 35, 36, 1, color);
+                draw_rect(x + 14, y, 1, 36, color);
+                draw_rect(x + 49, y, 1, 36, color);
+            }
+        }
+
+        let len = save_file.creatures.len();
+        let previous = selected;
+
+        let key = key::get_keypress();
+        global_hotkeys(key);
+        match key {
+            // up
+            0x48 | 0x11 => {
+                if !keystate_up {
+                    keystate_up = true;
+                    selected = selected.saturating_sub(COLUMNS);
+                }
+            }
+            0xc8 | 0x91 => {
+                keystate_up = false;
+            }
+            // down
+            0x50 | 0x1f => {
+                if !keystate_down {
+                    keystate_down = true;
+                    if selected + COLUMNS < len {
+                        selected += COLUMNS;
+                    }
+                }
+            }
+            0xd0 | 0x9f => {
+                keystate_down = false;
+            }
+            // left (on to the previous page at the start of one)
+            0x4b | 0x1e => {
+                if !keystate_left {
+                    keystate_left = true;
+                    selected = selected.saturating_sub(1);
+                }
+            }
+            0xcb | 0x9e => {
+                keystate_left = false;
+            }
+            // right (on to the next page at the end of one)
+            0x4d | 0x20 => {
+                if !keystate_right {
+                    keystate_right = true;
+                    if selected + 1 < len {
+                        selected += 1;
+                    }
+                }
+            }
+            0xcd | 0xa0 => {
+                keystate_right = false;
+            }
+            // page up
+            0x49 => {
+                if !keystate_page {
+                    keystate_page = true;
+                    selected = selected.saturating_sub(PAGE_SIZE);
+                }
+            }
+            // page down
+            0x51 => {
+                if !keystate_page {
+                    keystate_page = true;
+                    if selected + PAGE_SIZE < len {
+                        selected += PAGE_SIZE;
+                    }
+                }
+            }
+            0xc9 | 0xd1 => {
+                keystate_page = false;
+            }
+
+            // enter
+            0x1c => {
+                keystate_enter = true;
+            }
+            // enter release: show the creature
+            0x9c => {
+                if keystate_enter && !confirm_delete {
+                    if let Some(saved) = save_file.creatures.get(selected) {
+                        play_click_2();
+                        let creature = saved.creature;
+                        set_creature_palette(palette, &creature);
+                        let origin = Origin::Saved(saved.saved_at);
+                        present_creature(assets, &creature, palette, origin);
+                        draw_page(save_file, page, palette);
+                        draw_details(save_file, selected);
+                    }
+                }
+                keystate_enter = false;
+            }
+
+            // E: open in the editor
+            0x12 => {
+                if !keystate_edit && !confirm_delete {
+                    keystate_edit = true;
+                    if let Some(saved) = save_file.creatures.get(selected) {
+                        play_click_2();
+                        return GalleryOutcome::Edit(saved.creature);
+                    }
+                }
+            }
+            0x92 => {
+                keystate_edit = false;
+            }
+
+            // delete: ask for confirmation
+            0x53 => {
+                if !keystate_delete && selected < len {
+                    keystate_delete = true;
+                    confirm_delete = true;
+                    play_click_1();
+                    unsafe {
+                        draw_rect(0, 166, 320, 20, COLOR_BACKGROUND);
+                    }
+                    small_font.draw_text(61, 171, "Delete creature? Y/N", COLOR_HIGHLIGHT);
+                }
+            }
+            0xd3 => {
+                keystate_delete = false;
+            }
+            // Y: confirm deletion
+            0x15 => {
+                if confirm_delete {
+                    confirm_delete = false;
+                    play_click_2();
+                    save_file.creatures.remove(selected);
+                    check_saved(assets, save_file.save());
+                    selected = selected.min(save_file.creatures.len().saturating_sub(1));
+                    page = selected / PAGE_SIZE;
+                    draw_page(save_file, page, palette);
+                    draw_details(save_file, selected);
+                }
+            }
+            // N: cancel deletion
+            0x31 => {
+                if confirm_delete {
+                    confirm_delete = false;
+                    play_click_1();
+                    draw_details(save_file, selected);
+                }
+            }
+
+            // escape release: back to the main menu
+            0x81 => {
+                play_click_1();
+                return GalleryOutcome::Back;
+            }
+
+            _ => {}
+        }
+
+        if selected != previous {
+            play_click_1();
+            confirm_delete = false;
+            if selected / PAGE_SIZE != page {
+                page = selected / PAGE_SIZE;
+                draw_page(save_file, page, palette);
+            }
+            draw_details(save_file, selected);
+        }
+
+        adlib_player.poll(14_400);
+    }
+}
@@ -3,29 +3,33 @@ use alloc::{format, vec};
 use dos_x::vga::{self, read_video_buffer_rect, vsync, Palette};
 use minipng::{BitDepth, ImageData};
 
+use crate::catalog::{PartKind, Rarity, CATALOG, NUM_PART_KINDS};
 use crate::creature::CreatureParams;
 
 pub const COLOR_HIGHLIGHT: u8 = 252;
 pub const COLOR_BACKGROUND: u8 = 253;
 pub const COLOR_WHITE: u8 = 254;
 pub const COLOR_BLACK: u8 = 1;
+/// the colors of the creature rarity tiers above common
+pub const COLOR_UNCOMMON: u8 = 249;
+pub const COLOR_RARE: u8 = 250;
+pub const COLOR_LEGENDARY: u8 = 251;
+
+/// the first palette index of the creature's body color ramp (4 colors)
+pub const BODY_RAMP: u8 = 3;
+/// the first palette index of the creature's secondary color ramp (4 colors)
+pub const SECONDARY_RAMP: u8 = 13;
 
 // embed images into the binary
-static CREATURE_SHAPES: &[u8] = include_bytes!("../resources/creature-shapes.png");
-static CREATURE_EYES: &[u8] = include_bytes!("../resources/creature-eyes.png");
-static CREATURE_MOUTHS: &[u8] = include_bytes!("../resources/creature-mouths.png");
-static CREATURE_LEGS: &[u8] = include_bytes!("../resources/creature-legs.png");
-static CREATURE_ARMS: &[u8] = include_bytes!("../resources/creature-arms.png");
+// (creature sprite sheets are declared in the part catalog)
 static BIGFONT_PNG: &[u8] = include_bytes!("../resources/bigfont.png");
 static SMALLFONT_PNG: &[u8] = include_bytes!("../resources/font.png");
 
 #[derive(Debug)]
 pub struct CreatureAssets {
-    pub shapes_image: ImageAsset,
-    pub eyes_image: ImageAsset,
-    pub mouths_image: ImageAsset,
-    pub legs_image: ImageAsset,
-    pub arms_image: ImageAsset,
+    /// one sprite sheet per part category,
+    /// `None` for categories without sprites
+    pub sheets: [Option<ImageAsset>; NUM_PART_KINDS],
 }
 
 /// owned image asset (always 8-bit indexed)
@@ -40,102 +44,66 @@ pub struct ImageAsset {
 impl CreatureAssets {
     /// Load all creature assets.
     pub fn load() -> CreatureAssets {
-        // load creature shapes
-        let shapes_image = Self::load_asset(CREATURE_SHAPES);
-
-        // load creature eyes
-        let eyes_image = Self::load_asset(CREATURE_EYES);
-
-        // load creature mouths
-        let mouths_image = Self::load_asset(CREATURE_MOUTHS);
-
-        // load creature legs
-        let legs_image = Self::load_asset(CREATURE_LEGS);
-
-        // load creature arms
-        let arms_image = Self::load_asset(CREATURE_ARMS);
-
-        CreatureAssets {
-            shapes_image,
-            eyes_image,
-            mouths_image,
-            legs_image,
-            arms_image,
-        }
-    }
-
-    /// Render the creature into a buffer.
-    pub fn render_creature(&self, params: &CreatureParams, buffer: &mut [u8; 32 * 32]) {
-        // render creature legs first
-        let legs_index = params.legs as u32;
-        let legs_x = legs_index * 32;
-        for j in 9..32 {
-            for i in 0..32 {
-                let src_offset = (j * self.legs_image.width + legs_x + i) as usize;
-                let leg_pixel = self.legs_image.pixel_data[src_offset];
-                if leg_pixel != 0 {
-                    let dst_offset = (j * 32 + i) as usize;
-                    buffer[dst_offset] = leg_pixel;
-                }
-            }
-        }
-
-        // render creature shape to buffer
-        let shape_index = params.shape as u32;
-        let shape_x = shape_index * 32;
-
-        for row in 1..31 {
-            for col in 1..31 {
-                let src_offset = (row * self.shapes_image.width + shape_x + col) as usize;
-                let shape_pixel = self.shapes_image.pixel_data[src_offset];
-                if shape_pixel != 0 {
-                    let dst_offset = (row * 32 + col) as usize;
-                    buffer[dst_offset] = shape_pixel;
-                }
-            }
-        }
-
-        // render arms
-        let arms_index = params.arms as u32;
-        let arms_x = arms_index * 32;
-        for j in 2..32 {
-            for i in 0..32 {
-                let src_offset = (j * self.arms_image.width + arms_x + i) as usize;
-                let arm_pixel = self.arms_image.pixel_data[src_offset];
-                if arm_pixel != 0 {
-                    let dst_offset = (j * 32 + i) as usize;
-                    buffer[dst_offset] = arm_pixel;
-                }
+        let sheets = PartKind::ALL.map(|kind| {
+            let category = kind.category();
+            let image = Self::load_asset(category.sprite_sheet?);
+            // make sure that the sprite sheet has all parts
+            if image.width < category.count as u32 * 32 || image.height < 32 {
+                panic!(
+                    "{} sprite sheet is too small for {} parts",
+                    category.label, category.count
+                );
             }
-        }
+            Some(image)
+        });
 
-        // render mouth
-        let mouth_index = params.mouth as u32;
-        let mouth_x = mouth_index * 32;
-
-        for j in 5..28 {
-            for i in 2..30 {
-                let src_offset = (j * self.mouths_image.width + mouth_x + i) as usize;
-                let mouth_pixel = self.mouths_image.pixel_data[src_offset];
-                if mouth_pixel != 0 {
-                    let dst_offset = (j * 32 + i) as usize;
-                    buffer[dst_offset] = mouth_pixel;
-                }
-            }
-        }
+        CreatureAssets { sheets }
+    }
 
-        // draw eyes on top
-        let eyes_index = params.eyes as u32;
-        let eyes_x = eyes_index * 32;
-
-        // we use a tiny trick here, since we do not expect eye pixels around the boundaries
-        for j in 2..25 {
-            for i in 3..29 {
-                let src_offset = (j * self.eyes_image.width + eyes_x + i) as usize;
-                let eye_pixel = self.eyes_image.pixel_data[src_offset];
-                if eye_pixel != 0 {
-                    let dst_offset = (j * 32 + i) as usize;
-                    buffer[dst_offset] = eye_pixel;
+    /// Render the creature into a buffer,
+    /// with its colors in the given palette slot
+    /// (or in the main creature ramps if `None`).
+    pub fn render_creature(
+        &self,
+        params: &CreatureParams,
+        buffer: &mut [u8; 32 * 32],
+        slot: Option<PaletteSlot>,
+    ) {
+        let body_ramp = slot.map_or(BODY_RAMP, |slot| slot.body_ramp());
+        let secondary_ramp = slot.map_or(SECONDARY_RAMP, |slot| slot.secondary_ramp());
+
+        // draw each layer on top of the previous ones
+        for &kind in CATALOG.layers {
+            let Some(image) = &self.sheets[kind as usize] else {
+                continue;
+            };
+            let category = kind.category();
+            let (cols, rows) = &category.bounds;
+            let part_x = params.part(kind) as u32 * 32;
+
+            for j in rows.clone() {
+                for i in cols.clone() {
+                    let src_offset = (j * image.width + part_x + i) as usize;
+                    let mut pixel = image.pixel_data[src_offset];
+                    if pixel != 0 {
+                        let dst_offset = (j * 32 + i) as usize;
+                        // skip pixels outside of the body if requested
+                        if category.clip_to_body
+                            && !(body_ramp..body_ramp + 4).contains(&buffer[dst_offset])
+                        {
+                            continue;
+                        }
+                        // move body colors over to the right ramp
+                        if (BODY_RAMP..BODY_RAMP + 4).contains(&pixel) {
+                            let ramp = if category.secondary_ramp {
+                                secondary_ramp
+                            } else {
+                                body_ramp
+                            };
+                            pixel = pixel - BODY_RAMP + ramp;
+                        }
+                        buffer[dst_offset] = pixel;
+                    }
                 }
             }
         }
@@ -143,13 +111,26 @@ impl CreatureAssets {
 
     /// Draw the creature to the screen at the given pixel coordinates.
     pub fn draw_creature(&self, params: &CreatureParams, x: i32, y: i32) {
+        self.draw_creature_in_slot(params, x, y, None);
+    }
+
+    /// Draw the creature to the screen at the given pixel coordinates,
+    /// with its colors in the given palette slot
+    /// (or in the main creature ramps if `None`).
+    pub fn draw_creature_in_slot(
+        &self,
+        params: &CreatureParams,
+        x: i32,
+        y: i32,
+        slot: Option<PaletteSlot>,
+    ) {
         let mut buffer = [0; 32 * 32];
 
         unsafe {
             read_video_buffer_rect(&mut buffer, (x, y), (32, 32));
         }
 
-        self.render_creature(params, &mut buffer);
+        self.render_creature(params, &mut buffer, slot);
 
         unsafe {
             dos_x::vga::blit_rect(&buffer, (32, 32), (0, 0, 32, 32), (x, y));
@@ -347,6 +328,32 @@ impl BitmapFont {
         (i * self.char_width as u32, j * self.char_height as u32)
     }
 
+    /// Split the text into lines which fit in the given width (in pixels),
+    /// breaking lines between words.
+    ///
+    /// A word too long for a line gets a line of its own.
+    pub fn wrap_text<'a>(&self, text: &'a str, width: u32) -> Vec<&'a str> {
+        let max_chars = (width / (self.char_width + Self::H_SPACING) as u32) as usize;
+        let mut lines = Vec::new();
+        let mut rest = text.trim();
+        while rest.len() > max_chars {
+            // break at the last space which fits,
+            // or else at the first space there is
+            let bytes = rest.as_bytes();
+            let end = bytes[..=max_chars]
+                .iter()
+                .rposition(|&b| b == b' ')
+                .or_else(|| bytes.iter().position(|&b| b == b' '))
+                .unwrap_or(rest.len());
+            lines.push(rest[..end].trim_end());
+            rest = rest[end..].trim_start();
+        }
+        if !rest.is_empty() {
+            lines.push(rest);
+        }
+        lines
+    }
+
     pub fn draw_text(&self, x: i32, y: i32, text: impl AsRef<str>, color: u8) {
         let img_width = self.char_width as u32 * Self::CHARS_PER_ROW as u32;
         let cw = self.char_width as u32;
@@ -384,6 +391,13 @@ impl BitmapFont {
 }
 
 pub fn init_palette(palette: &mut Palette, creature: &CreatureParams) {
+    fill_palette(palette, creature);
+    palette.set();
+}
+
+/// Fill in the game palette for the given creature
+/// without applying it to the display.
+pub fn fill_palette(palette: &mut Palette, creature: &CreatureParams) {
     // initialize with zeros
     palette.0.fill(0);
 
@@ -401,7 +415,14 @@ pub fn init_palette(palette: &mut Palette, creature: &CreatureParams) {
     // 10: red
     // 11: darker red
     // 12: brown
-    // 13..=251: unused
+    // 13: creature secondary light
+    // 14: creature secondary regular
+    // 15: creature secondary dark
+    // 16: creature secondary darker
+    // 17..=248: palette slots for more creatures (see `PaletteSlots`)
+    // 249: uncommon rarity (green)
+    // 250: rare rarity (blue)
+    // 251: legendary rarity (purple)
     // 252: highlight color (orange-ish)
     // 253: background color
     // 254: white
@@ -420,7 +441,7 @@ pub fn init_palette(palette: &mut Palette, creature: &CreatureParams) {
     palette.0[7] = 0x3c;
     palette.0[8] = 0x3c;
 
-    set_creature_palette(palette, creature);
+    fill_creature_palette(palette, creature);
 
     // light grey
     palette.0[21] = 0x32;
@@ -452,28 +473,243 @@ pub fn init_palette(palette: &mut Palette, creature: &CreatureParams) {
     palette.0[37] = 0x0e;
     palette.0[38] = 0x00;
 
+    // rarity colors, dark enough to read on any background
+    palette.0[249 * 3] = 0x00;
+    palette.0[249 * 3 + 1] = 0x24;
+    palette.0[249 * 3 + 2] = 0x08;
+    palette.0[250 * 3] = 0x06;
+    palette.0[250 * 3 + 1] = 0x10;
+    palette.0[250 * 3 + 2] = 0x34;
+    palette.0[251 * 3] = 0x28;
+    palette.0[251 * 3 + 1] = 0x04;
+    palette.0[251 * 3 + 2] = 0x30;
+
     // highlight color (orange)
     palette.0[252 * 3] = 63;
     palette.0[252 * 3 + 1] = 36;
     palette.0[252 * 3 + 2] = 0;
 
-    // background color (baby blue)
-    palette.0[253 * 3] = 26;
-    palette.0[253 * 3 + 1] = 50;
-    palette.0[253 * 3 + 2] = 63;
+    // background color (baby blue by default)
+    fill_background(palette);
 
     // ensure that the second last color (#254) is always white.
     palette.0[762] = 63;
     palette.0[763] = 63;
     palette.0[764] = 63;
     // the last color (#255) is always black.
+}
+
+/// the palette index of the color in which a rarity tier is shown
+pub fn rarity_color(rarity: Rarity) -> u8 {
+    match rarity {
+        Rarity::Common => COLOR_BLACK,
+        Rarity::Uncommon => COLOR_UNCOMMON,
+        Rarity::Rare => COLOR_RARE,
+        Rarity::Legendary => COLOR_LEGENDARY,
+    }
+}
+
+/// The background colors to choose from,
+/// with their names (as in the config file)
+pub static BACKGROUND_COLORS: [(&str, [u8; 3]); 6] = [
+    ("Baby blue", [26, 50, 63]),
+    ("Mint", [34, 58, 44]),
+    ("Lavender", [48, 42, 63]),
+    ("Peach", [63, 48, 38]),
+    ("Lemon", [63, 60, 34]),
+    ("Silver", [46, 46, 50]),
+];
+
+/// the index of the background color in `BACKGROUND_COLORS`
+static mut BACKGROUND: usize = 0;
+
+/// the index of the current background color in `BACKGROUND_COLORS`
+pub fn background() -> usize {
+    unsafe { BACKGROUND }
+}
+
+/// Choose the background color (an index in `BACKGROUND_COLORS`)
+/// without applying it to the palette.
+pub fn set_background(index: usize) {
+    unsafe {
+        BACKGROUND = index % BACKGROUND_COLORS.len();
+    }
+}
+
+/// Choose the background color (an index in `BACKGROUND_COLORS`)
+/// and apply it to the display right away.
+pub fn apply_background(palette: &mut Palette, index: usize) {
+    set_background(index);
+    fill_background(palette);
     palette.set();
 }
 
+/// write the background color to the palette
+fn fill_background(palette: &mut Palette) {
+    let (_, color) = BACKGROUND_COLORS[background()];
+    let i = COLOR_BACKGROUND as usize * 3;
+    palette.0[i..i + 3].copy_from_slice(&color);
+}
+
+/// A palette slot with the colors of one creature,
+/// so that it can be drawn next to other creatures
+/// (see [`PaletteSlots`])
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct PaletteSlot(u8);
+
+impl PaletteSlot {
+    /// the first palette index of the slot's body color ramp
+    pub fn body_ramp(self) -> u8 {
+        self.0
+    }
+
+    /// the first palette index of the slot's secondary color ramp
+    pub fn secondary_ramp(self) -> u8 {
+        self.0 + 4
+    }
+
+    /// Set the creature's colors in this slot.
+    pub fn set_colors(self, palette: &mut Palette, creature: &CreatureParams) {
+        let body = self.body_ramp() as usize * 3;
+        palette.0[body..body + 12].copy_from_slice(&creature.body_colors());
+        let secondary = self.secondary_ramp() as usize * 3;
+        palette.0[secondary..secondary + 12].copy_from_slice(&creature.secondary_colors());
+        palette.set();
+    }
+}
+
+/// Allocator of palette slots,
+/// for showing many creatures with different colors at once.
+///
+/// Each slot takes 8 of the unused palette indices (17 to 248):
+/// the body color ramp and then the secondary color ramp.
+#[derive(Debug, Default)]
+pub struct PaletteSlots {
+    /// one bit for each slot in use
+    used: u32,
+}
+
+impl PaletteSlots {
+    /// the first palette index of the first slot
+    const FIRST: u8 = 17;
+    /// the number of palette indices in each slot
+    const SLOT_SIZE: u8 = 8;
+    /// the number of slots available
+    pub const NUM_SLOTS: u32 = 29;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take a free slot and set the creature's colors in it,
+    /// or return `None` if all slots are taken.
+    pub fn alloc(
+        &mut self,
+        palette: &mut Palette,
+        creature: &CreatureParams,
+    ) -> Option<PaletteSlot> {
+        let i = (!self.used).trailing_zeros();
+        if i >= Self::NUM_SLOTS {
+            return None;
+        }
+        self.used |= 1 << i;
+        let slot = PaletteSlot(Self::FIRST + i as u8 * Self::SLOT_SIZE);
+        slot.set_colors(palette, creature);
+        Some(slot)
+    }
+
+    /// Give all slots back.
+    pub fn clear(&mut self) {
+        self.used = 0;
+    }
+}
+
 pub fn set_creature_palette(palette: &mut Palette, creature: &CreatureParams) {
+    fill_creature_palette(palette, creature);
+    palette.set();
+}
+
+/// write the creature's color ramps to the palette
+/// without applying it to the display
+fn fill_creature_palette(palette: &mut Palette, creature: &CreatureParams) {
     // 4 colors, 3 samples each
     const COLOR_SAMPLES: usize = 4 * 3;
     let body_colors: [u8; COLOR_SAMPLES] = creature.body_colors();
-    palette.0[9..9 + COLOR_SAMPLES].copy_from_slice(&body_colors);
-    palette.set();
+    let body = BODY_RAMP as usize * 3;
+    palette.0[body..body + COLOR_SAMPLES].copy_from_slice(&body_colors);
+    let secondary_colors: [u8; COLOR_SAMPLES] = creature.secondary_colors();
+    let secondary = SECONDARY_RAMP as usize * 3;
+    palette.0[secondary..secondary + COLOR_SAMPLES].copy_from_slice(&secondary_colors);
+}
+
+/// Encode an 8-bit indexed image as a PCX file
+/// (version 5, 256 colors, run-length encoded),
+/// scaled up by an integer factor.
+///
+/// The palette is in the VGA format (6 bits per sample).
+pub fn encode_pcx(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    scale: u32,
+    palette: &[u8; 768],
+) -> Vec<u8> {
+    let out_width = width * scale;
+    let out_height = height * scale;
+    // scanlines must have an even number of bytes
+    let bytes_per_line = out_width.next_multiple_of(2);
+
+    let mut data = Vec::with_capacity(128 + (bytes_per_line * out_height) as usize + 769);
+
+    // header
+    data.extend_from_slice(&[
+        0x0a, // manufacturer (ZSoft)
+        5,    // version
+        1,    // encoding (RLE)
+        8,    // bits per pixel
+    ]);
+    for v in [0, 0, out_width - 1, out_height - 1, 72, 72] {
+        // x min, y min, x max, y max, horizontal and vertical DPI
+        data.extend_from_slice(&(v as u16).to_le_bytes());
+    }
+    // 16-color palette (unused), reserved byte, 1 color plane
+    data.extend_from_slice(&[0; 48]);
+    data.extend_from_slice(&[0, 1]);
+    data.extend_from_slice(&(bytes_per_line as u16).to_le_bytes());
+    // palette info (color), then the rest of the header is zeros
+    data.extend_from_slice(&1u16.to_le_bytes());
+    data.resize(128, 0);
+
+    // image data, one scanline at a time
+    let mut line = Vec::with_capacity(bytes_per_line as usize);
+    for y in 0..out_height {
+        line.clear();
+        let row = &pixels[((y / scale) * width) as usize..][..width as usize];
+        for &pixel in row {
+            line.extend(core::iter::repeat_n(pixel, scale as usize));
+        }
+        line.resize(bytes_per_line as usize, 0);
+
+        let mut i = 0;
+        while i < line.len() {
+            let value = line[i];
+            let run = line[i..]
+                .iter()
+                .take(63)
+                .take_while(|&&p| p == value)
+                .count();
+            // values with the two top bits set must be written as a run
+            if run > 1 || value >= 0xc0 {
+                data.push(0xc0 | run as u8);
+            }
+            data.push(value);
+            i += run;
+        }
+    }
+
+    // 256-color palette, with 8 bits per sample
+    data.push(0x0c);
+    data.extend(palette.iter().map(|&c| c << 2 | c >> 4));
+
+    data
 }
@@ -0,0 +1,178 @@
+//! A small GIF89a encoder for animated images
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// the number of bits of each pixel
+/// (and the minimum LZW code size)
+const MIN_CODE_SIZE: u8 = 8;
+/// the LZW code which resets the code table
+const CLEAR_CODE: u16 = 1 << MIN_CODE_SIZE;
+/// the LZW code at the end of each image
+const END_CODE: u16 = CLEAR_CODE + 1;
+/// GIF does not allow codes with more than 12 bits
+const MAX_CODES: usize = 1 << 12;
+/// marks the absence of a code in the code table
+const NO_CODE: u16 = u16::MAX;
+
+/// Encoder of 256-color animated GIF images,
+/// looping forever,
+/// with every frame scaled up by an integer factor
+pub struct GifEncoder {
+    data: Vec<u8>,
+    width: u16,
+    height: u16,
+    scale: u16,
+}
+
+impl GifEncoder {
+    /// Start a GIF image of the given size (before scaling)
+    /// and VGA palette (6 bits per sample).
+    pub fn new(width: u16, height: u16, scale: u16, palette: &[u8; 768]) -> Self {
+        let mut data = Vec::new();
+        data.extend_from_slice(b"GIF89a");
+
+        // logical screen descriptor:
+        // a global color table with 256 colors (8 bits per sample)
+        data.extend_from_slice(&(width * scale).to_le_bytes());
+        data.extend_from_slice(&(height * scale).to_le_bytes());
+        data.extend_from_slice(&[0xf7, 0, 0]);
+        data.extend(palette.iter().map(|&c| c << 2 | c >> 4));
+
+        // NETSCAPE2.0 application extension, to loop forever
+        data.extend_from_slice(&[0x21, 0xff, 11]);
+        data.extend_from_slice(b"NETSCAPE2.0");
+        data.extend_from_slice(&[3, 1, 0, 0, 0]);
+
+        GifEncoder {
+            data,
+            width,
+            height,
+            scale,
+        }
+    }
+
+    /// Add a frame with the given 8-bit indexed pixels
+    /// (of the size given in [`new`](Self::new)),
+    /// shown for `delay` hundredths of a second.
+    pub fn add_frame(&mut self, pixels: &[u8], delay: u16) {
+        // graphic control extension (no transparency)
+        self.data.extend_from_slice(&[0x21, 0xf9, 4, 0]);
+        self.data.extend_from_slice(&delay.to_le_bytes());
+        self.data.extend_from_slice(&[0, 0]);
+
+        // image descriptor, covering the whole image
+        let (width, height) = (self.width * self.scale, self.height * self.scale);
+        self.data.push(0x2c);
+        self.data.extend_from_slice(&[0, 0, 0, 0]);
+        self.data.extend_from_slice(&width.to_le_bytes());
+        self.data.extend_from_slice(&height.to_le_bytes());
+        self.data.push(0);
+
+        // image data, scaled up on the fly
+        let (w, s) = (self.width as usize, self.scale as usize);
+        let scaled = (0..height as usize)
+            .flat_map(move |y| (0..width as usize).map(move |x| (y / s) * w + x / s))
+            .map(|i| pixels[i]);
+        self.data.push(MIN_CODE_SIZE);
+        let compressed = lzw_compress(scaled);
+        for block in compressed.chunks(255) {
+            self.data.push(block.len() as u8);
+            self.data.extend_from_slice(block);
+        }
+        self.data.push(0);
+    }
+
+    /// Finish the image, returning the contents of the GIF file.
+    pub fn finish(mut self) -> Vec<u8> {
+        self.data.push(0x3b);
+        self.data
+    }
+}
+
+/// Compress 8-bit pixels with GIF's flavor of LZW.
+fn lzw_compress(pixels: impl IntoIterator<Item = u8>) -> Vec<u8> {
+    // the code table, as a tree:
+    // each code is a string of pixels,
+    // with child codes for the same string followed by one more pixel
+    let mut first_child = vec![NO_CODE; MAX_CODES];
+    let mut next_sibling = vec![NO_CODE; MAX_CODES];
+    let mut last_pixel = vec![0u8; MAX_CODES];
+
+    let mut out = BitWriter::default();
+    let mut code_size = MIN_CODE_SIZE + 1;
+    let mut next_code = END_CODE + 1;
+    out.write(CLEAR_CODE, code_size);
+
+    let mut pixels = pixels.into_iter();
+    let Some(first) = pixels.next() else {
+        out.write(END_CODE, code_size);
+        return out.finish();
+    };
+    let mut current = first as u16;
+
+    for pixel in pixels {
+        // look for the current string followed by this pixel
+        let mut child = first_child[current as usize];
+        while child != NO_CODE && last_pixel[child as usize] != pixel {
+            child = next_sibling[child as usize];
+        }
+        if child != NO_CODE {
+            current = child;
+            continue;
+        }
+
+        out.write(current, code_size);
+
+        if (next_code as usize) < MAX_CODES {
+            // add the new string to the table
+            last_pixel[next_code as usize] = pixel;
+            next_sibling[next_code as usize] = first_child[current as usize];
+            first_child[current as usize] = next_code;
+            // the decoder grows the code size right after this code
+            if next_code == 1 << code_size {
+                code_size += 1;
+            }
+            next_code += 1;
+        } else {
+            // the table is full, start over
+            out.write(CLEAR_CODE, code_size);
+            first_child.fill(NO_CODE);
+            code_size = MIN_CODE_SIZE + 1;
+            next_code = END_CODE + 1;
+        }
+        current = pixel as u16;
+    }
+
+    out.write(current, code_size);
+    out.write(END_CODE, code_size);
+    out.finish()
+}
+
+/// Writer of codes with any number of bits,
+/// least significant bit first
+#[derive(Default)]
+struct BitWriter {
+    data: Vec<u8>,
+    bits: u32,
+    num_bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.bits |= (code as u32) << self.num_bits;
+        self.num_bits += size;
+        while self.num_bits >= 8 {
+            self.data.push(self.bits as u8);
+            self.bits >>= 8;
+            self.num_bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.num_bits > 0 {
+            self.data.push(self.bits as u8);
+        }
+        self.data
+    }
+}
@@ -3,30 +3,61 @@
 extern crate alloc;
 
 mod audio;
+mod bestiary;
+mod breed;
+mod cli;
+mod config;
 mod create;
-mod creature;
+mod export;
+mod file;
+mod gallery;
 mod gfx;
+mod gif;
 mod menu;
+mod options;
+mod picker;
+mod save;
+mod screenshot;
+mod time;
+
+// the creature logic lives in its own crate, to be tested on the host
+use createac_creature::{catalog, color, creature, stats};
 
 use alloc::format;
+use alloc::vec::Vec;
 use audio::sound_off;
 use dos_x::adlib::detect_adlib;
 use dos_x::djgpp::dos::delay;
 use dos_x::djgpp::dpmi::{__dpmi_int, __dpmi_regs};
 use dos_x::vga::Palette;
 
+use core::ffi::CStr;
 use core::panic::PanicInfo;
 use dos_x::vga::vsync;
 use dos_x::{djgpp::stdlib::exit, println};
-use tinyrand::{RandRange, Seeded};
+use tinyrand::{RandRange, Seeded, Xorshift};
 
-use crate::audio::{adlib_notes_off, load_player, music_off};
+use crate::audio::{
+    adlib_notes_off, load_player, music_off, play_click_1, play_click_2, sound_hotkeys,
+};
+use crate::bestiary::{bestiary_screen, Bestiary};
+use crate::breed::{breeding, BreedOutcome};
+use crate::catalog::PartKind;
+use crate::cli::Command;
+use crate::config::{set_key_bindings, Config};
 use crate::create::{main_game, MainGameOutcome};
 use crate::creature::CreatureParams;
+use crate::export::{export_creature, export_creature_to, ExportFormat};
+use crate::gallery::{gallery, GalleryOutcome};
 use crate::gfx::{
-    fade_out, init_palette, BitmapFont, CreatureAssets, COLOR_HIGHLIGHT, COLOR_WHITE,
+    fade_out, fill_palette, init_palette, set_background, set_creature_palette, BitmapFont,
+    CreatureAssets, PaletteSlots, COLOR_HIGHLIGHT, COLOR_WHITE,
 };
 use crate::menu::MenuOutcome;
+use crate::options::options_screen;
+use crate::save::SaveFile;
+use crate::screenshot::screenshot_hotkey;
+use crate::time::Timestamp;
 
 /// 16x16 floppy disk icon, raw 8-bit indexed data
 /// (already assumes game palette for B&W)
@@ -45,32 +76,139 @@ pub struct Assets {
 enum GameState {
     MainMenu,
     InGame,
-    PresentingCreature,
+    Breeding,
+    Gallery,
+    Bestiary,
+    Options,
+    /// present the creature of the day
+    CreatureOfTheDay,
+    /// present the current creature,
+    /// along with its parents if it was bred
+    PresentingCreature(Option<[CreatureParams; 2]>),
 }
 
 #[no_mangle]
 fn dos_main() {
     // process inputs
-    for arg in dos_x::argv() {
-        unsafe {
-            let arg = core::ffi::CStr::from_ptr(*arg);
-            if arg.to_bytes() == b"nosound" {
-                sound_off();
-                music_off();
+    // (the first one is the program name)
+    let args: Vec<&[u8]> = dos_x::argv()
+        .iter()
+        .skip(1)
+        .map(|arg| unsafe { CStr::from_ptr(*arg) }.to_bytes())
+        .collect();
+    let options = match cli::parse_args(&args) {
+        None => {
+            // (the problem was already reported)
+            unsafe {
+                exit(1);
             }
+            return;
         }
+        Some(Command::Export {
+            creature,
+            path,
+            format,
+            scale,
+        }) => {
+            if !export_headless(&creature, &path, format, scale) {
+                unsafe {
+                    exit(1);
+                }
+            }
+            return;
+        }
+        Some(Command::Help) => {
+            cli::print_usage();
+            return;
+        }
+        Some(Command::Play(options)) => options,
+    };
+
+    // read the options in the config file
+    // (the command line takes precedence over them)
+    let (config, reported) = Config::load();
+    if reported {
+        // (the game switches to graphics mode right away,
+        // which would clear the screen before the player could read it)
+        println!("Press any key to continue");
+        wait_for_key();
+    }
+    set_background(config.background);
+    set_key_bindings(config.keys);
+
+    if options.no_music || !config.music {
+        music_off();
+    }
+    if options.no_sfx || !config.effects {
+        sound_off();
     }
+    // the creature to start with, unless a random one
+    // (a given seed means a random one, so that the session can be reproduced)
+    let creature = match options.seed {
+        Some(_) => options.creature,
+        None => options.creature.or(config.creature),
+    };
 
     // seed the RNG
+    // (from the clock, unless a seed was given)
+    let seed = options
+        .seed
+        .unwrap_or_else(|| dos_x::clock::get_system_clock_ticks() as u64);
+    unsafe {
+        SEED = seed;
+    }
+
+    let rng = seeded_rng(seed);
+    run(rng, options.seed, creature, config);
+}
 
-    let time = dos_x::clock::get_system_clock_ticks();
-    let seed = 0xc5a0_63ab_2366_2d31 ^ ((time as u64) << 32 | (time as u64));
+/// the seed of the random number generator in this session,
+/// reported if the game panics so that the session can be reproduced
+static mut SEED: u64 = 0;
+
+/// Create the random number generator of the session.
+///
+/// All random decisions in the game come from this one generator,
+/// so that the same seed always leads to the same creatures.
+fn seeded_rng(seed: u64) -> Xorshift {
+    // (spread the bits of small seeds such as clock ticks)
+    Xorshift::seed(0xc5a0_63ab_2366_2d31 ^ seed.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+}
 
-    let rng = tinyrand::Xorshift::seed(seed);
-    run(rng);
+/// Export a creature to an image file
+/// without switching to graphics mode or using the sound card,
+/// returning whether it was successful.
+fn export_headless(
+    creature: &CreatureParams,
+    path: &CStr,
+    format: ExportFormat,
+    scale: u32,
+) -> bool {
+    let creature_assets = CreatureAssets::load();
+    // (the palette is never applied to the display)
+    let mut palette = Palette::new([0u8; 768]);
+    fill_palette(&mut palette, creature);
+
+    let file_name = path.to_str().unwrap_or_default();
+    if export_creature_to(&creature_assets, creature, &palette, format, scale, path) {
+        println!("Exported {creature} to {file_name}");
+        true
+    } else {
+        println!("Could not write {file_name}");
+        false
+    }
 }
 
-fn run(mut rng: impl RandRange<u16>) {
+/// Run the game.
+///
+/// `given_seed` is the seed from the command line, if any,
+/// which is shown in the main menu.
+fn run(
+    mut rng: impl RandRange<u16>,
+    given_seed: Option<u64>,
+    creature: Option<CreatureParams>,
+    mut config: Config,
+) {
     println!("Create-a-Creature by E_net4 (2025)");
 
     unsafe {
@@ -90,8 +228,11 @@ fn run(mut rng: impl RandRange<u16>) {
         dos_x::vga::draw_rect(0, 0, 320, 200, 253);
     }
 
-    // initialize random creature
-    let mut creature = CreatureParams::new_random(&mut rng);
+    // load the player's collection
+    let mut save_file = SaveFile::load();
+
+    // use the creature given by code, or initialize a random one
+    let mut creature = creature.unwrap_or_else(|| CreatureParams::new_random(&mut rng));
 
     // grab palette and apply it to VGA display
     let mut palette = Palette::new([0u8; 768]);
@@ -108,16 +249,15 @@ fn run(mut rng: impl RandRange<u16>) {
         dos_x::vga::blit_rect(FLOPPY_DATA, (16, 16), (0, 0, 16, 16), (152, 92));
     }
 
-    match detect_adlib() {
-        0 => {
-            println!("No Adlib sound card detected, music disabled");
-            music_off();
-        }
-        _ => {
-            println!("Adlib sound card detected");
-        }
-    };
-    let adlib_player = load_player();
+    let adlib_detected = detect_adlib() != 0;
+    if adlib_detected {
+        println!("Adlib sound card detected");
+    } else {
+        println!("No Adlib sound card detected, music disabled");
+        music_off();
+    }
+    let adlib_player = load_player(adlib_detected);
+    adlib_player.set_volume(config.volume);
 
     // load creature assets
     let creature_assets = CreatureAssets::load();
@@ -132,15 +272,32 @@ fn run(mut rng: impl RandRange<u16>) {
         adlib_player,
     };
 
+    let mut bestiary = Bestiary::load();
+
     let mut state = GameState::MainMenu;
     loop {
         match state {
             GameState::MainMenu => {
-                let outcome = menu::menu(&assets, &creature);
+                let outcome = menu::menu(&assets, &creature, given_seed);
                 match outcome {
                     MenuOutcome::Enter => {
                         state = GameState::InGame;
                     }
+                    MenuOutcome::Breed => {
+                        state = GameState::Breeding;
+                    }
+                    MenuOutcome::Gallery => {
+                        state = GameState::Gallery;
+                    }
+                    MenuOutcome::Bestiary => {
+                        state = GameState::Bestiary;
+                    }
+                    MenuOutcome::Options => {
+                        state = GameState::Options;
+                    }
+                    MenuOutcome::CreatureOfTheDay => {
+                        state = GameState::CreatureOfTheDay;
+                    }
                     MenuOutcome::Exit => {
                         break;
                     }
@@ -151,12 +308,67 @@ fn run(mut rng: impl RandRange<u16>) {
                 match outcome {
                     MainGameOutcome::Exit => break,
                     MainGameOutcome::SaveCreature => {
-                        state = GameState::PresentingCreature;
+                        if bestiary.log(&creature) {
+                            check_saved(&assets, bestiary.save());
+                        }
+                        state = GameState::PresentingCreature(None);
+                    }
+                }
+            }
+            GameState::Breeding => {
+                // start with the current creature and a random one
+                let mut parents = [creature, CreatureParams::new_random(&mut rng)];
+                let outcome = breeding(&assets, &mut parents, &mut palette, &mut rng);
+                match outcome {
+                    BreedOutcome::Back => {
+                        set_creature_palette(&mut palette, &creature);
+                        state = GameState::MainMenu;
+                    }
+                    BreedOutcome::Offspring(offspring) => {
+                        creature = offspring;
+                        state = GameState::PresentingCreature(Some(parents));
                     }
                 }
             }
-            GameState::PresentingCreature => {
-                present_creature(&assets, &creature);
+            GameState::Gallery => {
+                let outcome = gallery(&assets, &mut save_file, &mut palette);
+                match outcome {
+                    GalleryOutcome::Back => {
+                        set_creature_palette(&mut palette, &creature);
+                        state = GameState::MainMenu;
+                    }
+                    GalleryOutcome::Edit(saved) => {
+                        creature = saved;
+                        set_creature_palette(&mut palette, &creature);
+                        state = GameState::InGame;
+                    }
+                }
+            }
+            GameState::Bestiary => {
+                bestiary_screen(&assets, &bestiary);
+                state = GameState::MainMenu;
+            }
+            GameState::CreatureOfTheDay => {
+                let today = Timestamp::now();
+                let daily = CreatureParams::of_the_day(today.year, today.month, today.day);
+                set_creature_palette(&mut palette, &daily);
+                present_creature(&assets, &daily, &mut palette, Origin::OfTheDay(today));
+                // (it is not the player's creature, so it is not saved)
+                set_creature_palette(&mut palette, &creature);
+                state = GameState::MainMenu;
+            }
+            GameState::Options => {
+                options_screen(&assets, &mut config, &mut palette);
+                state = GameState::MainMenu;
+            }
+            GameState::PresentingCreature(parents) => {
+                let origin = match &parents {
+                    Some(parents) => Origin::Bred(parents),
+                    None => Origin::Created,
+                };
+                present_creature(&assets, &creature, &mut palette, origin);
+                // the creature was accepted, keep it in the collection
+                check_saved(&assets, save_file.append(&creature));
                 // and return to main menu
                 state = GameState::MainMenu;
             }
@@ -176,7 +388,69 @@ fn run(mut rng: impl RandRange<u16>) {
     println!("Thank you for playing!");
 }
 
-fn present_creature(assets: &Assets, creature: &CreatureParams) {
+/// The creature jumping in place on the presentation screen
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Jump {
+    /// the vertical offset from the resting position
+    /// (from -3 at the top of the jump to 10 as it lands)
+    pub y: i32,
+    speed: i32,
+}
+
+impl Default for Jump {
+    fn default() -> Self {
+        Jump {
+            y: 0,
+            speed: -Self::SPEED,
+        }
+    }
+}
+
+impl Jump {
+    const SPEED: i32 = 14;
+    /// the number of frames from one landing to the next
+    pub const LOOP_FRAMES: u32 = 28;
+
+    /// the jump right as the creature lands
+    pub fn landed() -> Self {
+        Jump {
+            y: 10,
+            speed: -Self::SPEED,
+        }
+    }
+
+    /// Move on to the next frame,
+    /// returning whether the creature landed.
+    pub fn step(&mut self) -> bool {
+        self.speed += 1;
+        self.y += self.speed / 5;
+        if self.y > 10 {
+            *self = Self::landed();
+            return true;
+        }
+        false
+    }
+}
+
+/// How a creature came about, as told on the presentation screen
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum Origin<'a> {
+    /// designed by the player
+    Created,
+    /// bred from these parents
+    Bred(&'a [CreatureParams; 2]),
+    /// the creature of the day for this date
+    OfTheDay(Timestamp),
+    /// shown from the player's collection, saved at this time
+    Saved(Timestamp),
+}
+
+fn present_creature(
+    assets: &Assets,
+    creature: &CreatureParams,
+    palette: &mut Palette,
+    origin: Origin,
+) {
     let Assets {
         adlib_player,
         creature_assets,
@@ -191,90 +465,317 @@ fn present_creature(assets: &Assets, creature: &CreatureParams) {
         dos_x::vga::draw_rect(0, 0, 320, 200, 253);
     }
 
-    small_font.draw_text(86, 20, "You have created", gfx::COLOR_BLACK);
+    // other creatures on screen have their own colors
+    let mut slots = PaletteSlots::new();
+
+    match origin {
+        Origin::Created => {
+            small_font.draw_text(86, 20, "You have created", gfx::COLOR_BLACK);
+        }
+        Origin::Bred(_) => {
+            small_font.draw_text(101, 20, "You have bred", gfx::COLOR_BLACK);
+        }
+        Origin::OfTheDay(_) => {
+            small_font.draw_text(74, 20, "Creature of the Day", gfx::COLOR_BLACK);
+        }
+        Origin::Saved(_) => {
+            small_font.draw_text(70, 20, "From your collection", gfx::COLOR_BLACK);
+        }
+    }
+
+    // show both parents beside the offspring, if bred
+    let parent_slots = match origin {
+        Origin::Bred(parents) => parents.map(|parent| slots.alloc(palette, &parent)),
+        _ => [None; 2],
+    };
+    let draw_parents = || {
+        if let Origin::Bred(parents) = origin {
+            for ((parent, slot), x) in parents.iter().zip(parent_slots).zip([40, 248]) {
+                creature_assets.draw_creature_in_slot(parent, x, 99, slot);
+            }
+            small_font.draw_text(20, 135, "Parent 1", gfx::COLOR_BLACK);
+            small_font.draw_text(228, 135, "Parent 2", gfx::COLOR_BLACK);
+        }
+    };
+    draw_parents();
 
     // print creature name
-    print_name(creature, big_font);
+    print_name(creature, big_font, small_font);
 
-    let mut keystate_enter = false;
+    // print the date under the name (centered)
+    let date_text = match origin {
+        Origin::OfTheDay(date) => Some(format!("{date}")),
+        Origin::Saved(saved_at) => Some(format!("Saved {saved_at}")),
+        _ => None,
+    };
+    if let Some(date_text) = date_text {
+        let x = (320 - (date_text.len() as i32 * 9)) / 2;
+        small_font.draw_text(x, 72, date_text, gfx::COLOR_BLACK);
+    }
+
+    // print creature code (centered)
+    let code_text = format!("Code: {}", creature.to_code());
+    let x = (320 - (code_text.len() as i32 * 9)) / 2;
+    small_font.draw_text(x, 145, &code_text, gfx::COLOR_BLACK);
+
+    // print creature rarity (centered, in the color of its tier)
+    let rarity = creature.rarity();
+    let x = (320 - (rarity.name().len() as i32 * 9)) / 2;
+    small_font.draw_text(x, 155, rarity.name(), gfx::rarity_color(rarity));
+
+    // the creature's description,
+    // shown in place of the jumping creatures on request
+    let description = format!("{}", creature.description());
+    let description_lines = small_font.wrap_text(&description, 306);
+    let mut describing = false;
 
-    const JUMP_SPEED: i32 = 14;
-    let mut var_y = 0;
-    let mut speed_y = -JUMP_SPEED;
+    let mut keystate_enter = false;
+    let mut keystate_export = false;
+    let mut keystate_describe = false;
+    // whether the creature has been exported
+    // (so that the export message stays on screen)
+    let mut exported = false;
+    // the scale of exported images
+    let mut export_scale = 2;
+
+    let mut jump = Jump::default();
     let mut num_jumps: u16 = 0;
 
     // pre-render creature
     let mut creature_render = [gfx::COLOR_BACKGROUND; 32 * 32];
-    creature_assets.render_creature(creature, &mut creature_render);
+    creature_assets.render_creature(creature, &mut creature_render, None);
     let creature_render = &creature_render[..];
 
+    // pre-render the creatures which appear later,
+    // relatives of a different color
+    let relative_renders = [1, 2].map(|k| {
+        let num_colors = PartKind::Color.count();
+        let relative = CreatureParams {
+            color: (creature.color + k) % num_colors,
+            custom_color: None,
+            ..*creature
+        };
+        let slot = slots.alloc(palette, &relative);
+        let mut render = [gfx::COLOR_BACKGROUND; 32 * 32];
+        creature_assets.render_creature(&relative, &mut render, slot);
+        render
+    });
+
     loop {
         unsafe {
             vsync();
         }
 
-        unsafe {
-            if num_jumps < 24 {
-                // clear screen in creature's place
-                dos_x::vga::draw_rect(144, 71, 32, 60, 253);
-            } else {
-                // after some time, more creatures will appear,
-                // so clear more
-                dos_x::vga::draw_rect(100, 71, 114, 60, 253);
+        // draw the jumping creatures,
+        // unless the description is shown in their place
+        if !describing {
+            unsafe {
+                if num_jumps < 24 {
+                    // clear screen in creature's place
+                    // (below the name and the date)
+                    dos_x::vga::draw_rect(144, 84, 32, 47, 253);
+                } else {
+                    // after some time, more creatures will appear,
+                    // so clear more
+                    dos_x::vga::draw_rect(100, 84, 114, 47, 253);
+                }
             }
-        }
-
-        // draw creature in center of screen
-        unsafe {
-            dos_x::vga::blit_rect(creature_render, (32, 32), (0, 0, 32, 32), (144, 89 + var_y));
-        }
 
-        if num_jumps >= 24 {
-            // draw more creatures
+            // draw creature in center of screen
             unsafe {
-                dos_x::vga::blit_rect(creature_render, (32, 32), (0, 0, 32, 32), (100, 89 + var_y));
-                dos_x::vga::blit_rect(creature_render, (32, 32), (0, 0, 32, 32), (188, 89 + var_y));
+                dos_x::vga::blit_rect(
+                    creature_render,
+                    (32, 32),
+                    (0, 0, 32, 32),
+                    (144, 89 + jump.y),
+                );
+            }
+
+            if num_jumps >= 24 && !matches!(origin, Origin::Bred(_)) {
+                // draw more creatures
+                unsafe {
+                    let [left, right] = &relative_renders;
+                    dos_x::vga::blit_rect(left, (32, 32), (0, 0, 32, 32), (100, 89 + jump.y));
+                    dos_x::vga::blit_rect(right, (32, 32), (0, 0, 32, 32), (188, 89 + jump.y));
+                }
             }
         }
 
-        speed_y += 1;
-        var_y += speed_y / 5;
-        if var_y > 10 {
-            var_y = 10;
-            speed_y = -JUMP_SPEED;
+        if jump.step() {
             num_jumps = (num_jumps + 1) & 0x3F;
         }
 
         adlib_player.poll(18_000);
 
+        let key = dos_x::key::get_keypress();
+        global_hotkeys(key);
+
         if can_proceed > 0 {
             can_proceed -= 1;
             continue;
         }
 
         small_font.draw_text(60, 165, "Press ENTER to continue", gfx::COLOR_BLACK);
+        if !exported {
+            let text = format!("P: PCX  G: GIF  Size (1-4): {export_scale}x");
+            let x = (320 - (text.len() as i32 * 9)) / 2;
+            small_font.draw_text(x, 178, text, gfx::COLOR_BLACK);
+        }
+        small_font.draw_text(97, 188, "D: Description", gfx::COLOR_BLACK);
 
         // check for ENTER key
-        let key = dos_x::key::get_keypress();
         if key == 0x1c {
             // key pressed
             keystate_enter = true;
         } else if key == 0x9c && keystate_enter {
             // key released
             break;
+        } else if (0x02..=0x05).contains(&key) {
+            // number keys 1 to 4: the scale of exported images
+            let scale = key as u32 - 1;
+            if scale != export_scale || exported {
+                play_click_1();
+                export_scale = scale;
+                exported = false;
+                unsafe {
+                    dos_x::vga::draw_rect(0, 178, 320, 8, gfx::COLOR_BACKGROUND);
+                }
+            }
+        } else if key == 0x19 || key == 0x22 {
+            // P: export a picture, G: export the animation
+            if !keystate_export {
+                keystate_export = true;
+                exported = true;
+                play_click_2();
+                let format = if key == 0x19 {
+                    ExportFormat::Pcx
+                } else {
+                    ExportFormat::Gif
+                };
+                let file_name =
+                    export_creature(creature_assets, creature, palette, format, export_scale);
+                let text = match file_name {
+                    Some(file_name) => format!("Exported {file_name}"),
+                    None => "Could not export".into(),
+                };
+                unsafe {
+                    dos_x::vga::draw_rect(0, 178, 320, 8, gfx::COLOR_BACKGROUND);
+                }
+                let x = (320 - (text.len() as i32 * 9)) / 2;
+                small_font.draw_text(x, 178, text, gfx::COLOR_BLACK);
+            }
+        } else if key == 0x99 || key == 0xa2 {
+            keystate_export = false;
+        } else if key == 0x20 {
+            // D: show the description (or the creatures again)
+            if !keystate_describe {
+                keystate_describe = true;
+                describing = !describing;
+                play_click_1();
+                unsafe {
+                    dos_x::vga::draw_rect(0, 84, 320, 60, gfx::COLOR_BACKGROUND);
+                }
+                if describing {
+                    // centered in the place of the creatures
+                    let y = 114 - description_lines.len() as i32 * 5;
+                    for (i, line) in description_lines.iter().enumerate() {
+                        let x = (320 - (line.len() as i32 * 9)) / 2;
+                        small_font.draw_text(x, y + i as i32 * 10, line, gfx::COLOR_BLACK);
+                    }
+                } else {
+                    draw_parents();
+                }
+            }
+        } else if key == 0xa0 {
+            keystate_describe = false;
+        }
+    }
+}
+
+/// Wait for a key to be pressed and released, in text mode.
+fn wait_for_key() {
+    let is_pressed = |key: u8| key != 0 && key & 0x80 == 0;
+    // let go of any key still held (like ENTER from the command line),
+    // then wait for a key to be pressed and then released
+    for pressed in [true, false, true] {
+        while is_pressed(dos_x::key::get_keypress()) == pressed {
+            unsafe {
+                vsync();
+            }
+        }
+    }
+}
+
+/// Wait for ENTER to be released, keeping the music going.
+///
+/// Scenes opened from a menu with ENTER start with the key still held,
+/// which they would otherwise take as a press of their own.
+pub(crate) fn wait_for_enter_release(assets: &Assets) {
+    loop {
+        unsafe {
+            vsync();
         }
+        if dos_x::key::get_keypress() != 0x1c {
+            return;
+        }
+        assets.adlib_player.poll(14_400);
     }
 }
 
+/// Tell the player if something could not be saved to disk.
+///
+/// Nothing much can be done about a full or read-only disk,
+/// so the game carries on after showing a short message
+/// at the bottom of the screen for a moment.
+pub(crate) fn check_saved(assets: &Assets, saved: bool) {
+    if saved {
+        return;
+    }
+    let text = "Could not save to disk";
+    unsafe {
+        vsync();
+        dos_x::vga::draw_rect(0, 188, 320, 12, gfx::COLOR_BACKGROUND);
+    }
+    let x = (320 - (text.len() as i32 * 9)) / 2;
+    assets
+        .small_font
+        .draw_text(x, 190, text, gfx::COLOR_HIGHLIGHT);
+    for _ in 0..90 {
+        unsafe {
+            vsync();
+        }
+        assets.adlib_player.poll(14_400);
+    }
+}
+
+/// Handle the hotkeys available in every scene:
+/// F12 for a screenshot, M and X to toggle music and sound effects.
+///
+/// Every scene loop calls this with the key read in that frame.
+pub(crate) fn global_hotkeys(key: u8) {
+    screenshot_hotkey(key);
+    sound_hotkeys(key);
+}
+
 /// print the creature's name at the center of the screen
 /// (with an exclamation point)
-pub(crate) fn print_name(creature: &CreatureParams, big_font: &BitmapFont) {
+pub(crate) fn print_name(
+    creature: &CreatureParams,
+    big_font: &BitmapFont,
+    small_font: &BitmapFont,
+) {
     let text = format!("{creature}!");
 
-    // centered
-    let x = (320 - (text.len() as i32 * 17)) / 2;
-    big_font.draw_text(x - 1, 51, &text, COLOR_WHITE);
-    big_font.draw_text(x, 52, text, COLOR_HIGHLIGHT);
+    // centered, with the small font if it does not fit on the screen
+    let (font, y) = if text.len() * 17 <= 320 {
+        (big_font, 51)
+    } else {
+        (small_font, 55)
+    };
+    let char_width = font.char_width as i32 + 1;
+    let x = (320 - (text.len() as i32 * char_width)) / 2;
+    font.draw_text(x - 1, y, &text, COLOR_WHITE);
+    font.draw_text(x, y + 1, text, COLOR_HIGHLIGHT);
 }
 
 #[panic_handler]
@@ -283,6 +784,8 @@ fn handle_panic(info: &PanicInfo) -> ! {
         // reset video mode
         dos_x::vga::set_video_mode(0x02);
         println!("Program aborted: {}", info);
+        let seed = SEED;
+        println!("Random seed: {seed} (run with /SEED={seed} to start the same way)");
         println!("This is likely a bug! Please reach out:");
         println!("    https://github.com/Enet4/createac/issues/new");
         // exit using libc
@@ -1,22 +1,34 @@
+use alloc::format;
 use dos_x::{key, vga::vsync};
 
 use crate::{
     audio::{play_click_1, play_click_2},
     creature::CreatureParams,
     gfx::{draw_arrow_left, draw_arrow_right, COLOR_BACKGROUND, COLOR_BLACK, COLOR_WHITE},
-    Assets,
+    global_hotkeys, Assets,
 };
 
 #[derive(Debug, Copy, Clone)]
 pub enum MenuOutcome {
     /// Enter create-a-creature mode
     Enter,
+    /// Enter breeding mode
+    Breed,
+    /// Browse the saved creatures
+    Gallery,
+    /// Show the bestiary
+    Bestiary,
+    /// Show the creature of the day
+    CreatureOfTheDay,
+    /// Change the game options
+    Options,
     /// Exit the game
     Exit,
 }
 
-/// Show and operate the main menu
-pub fn menu(assets: &Assets, creature: &CreatureParams) -> MenuOutcome {
+/// Show and operate the main menu,
+/// along with the random seed if it was given in the command line
+pub fn menu(assets: &Assets, creature: &CreatureParams, seed: Option<u64>) -> MenuOutcome {
     let Assets {
         adlib_player,
         creature_assets,
@@ -25,11 +37,35 @@ pub fn menu(assets: &Assets, creature: &CreatureParams) -> MenuOutcome {
         ..
     } = assets;
 
-    // simple menu screen with 2 choices
+    // simple menu screen with a few choices,
+    // laid out in two columns
+    const CHOICES: [(&str, MenuOutcome); 7] = [
+        ("Create!", MenuOutcome::Enter),
+        ("Breed!", MenuOutcome::Breed),
+        ("Gallery", MenuOutcome::Gallery),
+        ("Bestiary", MenuOutcome::Bestiary),
+        ("Daily", MenuOutcome::CreatureOfTheDay),
+        ("Options", MenuOutcome::Options),
+        ("Exit", MenuOutcome::Exit),
+    ];
+    const BASE_Y: i32 = 120;
+    const STRIDE: i32 = 18;
     let mut choice = 0;
 
+    // the position and width of each choice's text
+    let choice_rect = |i: usize| {
+        let (label, _) = CHOICES[i];
+        let width = label.len() as i32 * 17;
+        // centered in its column
+        let x = 80 + 160 * (i % 2) as i32 - width / 2;
+        let y = BASE_Y + STRIDE * (i / 2) as i32;
+        (x, y, width)
+    };
+
     let mut keystate_up = false;
     let mut keystate_down = false;
+    let mut keystate_left = false;
+    let mut keystate_right = false;
 
     // clear background with background color
     unsafe {
@@ -38,38 +74,47 @@ pub fn menu(assets: &Assets, creature: &CreatureParams) -> MenuOutcome {
     }
     big_font.draw_text(82, 22, "Create a", COLOR_WHITE);
     big_font.draw_text(83, 23, "Create a", COLOR_BLACK);
-    crate::print_name(creature, big_font);
+    crate::print_name(creature, big_font, small_font);
 
     creature_assets.draw_creature(creature, 144, 84);
 
-    big_font.draw_text(104, 126, "Create!", COLOR_BLACK);
-    big_font.draw_text(124, 152, "Exit", COLOR_BLACK);
+    for (i, (label, _)) in CHOICES.iter().enumerate() {
+        let (x, y, _) = choice_rect(i);
+        big_font.draw_text(x, y, label, COLOR_BLACK);
+    }
 
     small_font.draw_text(142, 189, "Eduardo Pinho, 2025", COLOR_BLACK);
+    if let Some(seed) = seed {
+        small_font.draw_text(4, 4, format!("Seed {seed}"), COLOR_BLACK);
+    }
 
     loop {
         unsafe {
             vsync();
         }
 
-        const ARROW_LEFT: u32 = 86;
-        const ARROW_RIGHT: u32 = 234;
-        // clear regions with selection arrow
-        unsafe {
-            dos_x::vga::draw_rect(ARROW_LEFT as i32, 127, 8, 38, COLOR_BACKGROUND);
-            dos_x::vga::draw_rect(ARROW_RIGHT as i32, 127, 8, 38, COLOR_BACKGROUND);
+        // selection arrows around the chosen option,
+        // clearing them around all other options
+        for i in 0..CHOICES.len() {
+            let (x, y, width) = choice_rect(i);
+            let color = if i == choice {
+                COLOR_BLACK
+            } else {
+                COLOR_BACKGROUND
+            };
+            draw_arrow_right((x - 10) as u32, (y + 4) as u32, color);
+            draw_arrow_left((x + width + 4) as u32, (y + 4) as u32, color);
         }
 
-        let selection_y = 130 + choice as u32 * 25;
-        draw_arrow_right(ARROW_LEFT, selection_y, COLOR_BLACK);
-        draw_arrow_left(ARROW_RIGHT, selection_y, COLOR_BLACK);
-
-        // check up down key presses
+        // check arrow key presses
         let key = key::get_keypress();
+        global_hotkeys(key);
         match key {
             k if (k & 0x80) != 0 => {
                 keystate_up = false;
                 keystate_down = false;
+                keystate_left = false;
+                keystate_right = false;
             }
             0x48 => {
                 // up arrow
@@ -77,8 +122,10 @@ pub fn menu(assets: &Assets, creature: &CreatureParams) -> MenuOutcome {
                     keystate_up = true;
 
                     // change choice
-                    choice = 0;
-                    play_click_1();
+                    if choice >= 2 {
+                        choice -= 2;
+                        play_click_1();
+                    }
                 }
             }
             0x50 => {
@@ -87,17 +134,39 @@ pub fn menu(assets: &Assets, creature: &CreatureParams) -> MenuOutcome {
                     keystate_down = true;
 
                     // change choice
-                    choice = 1;
-                    play_click_1();
+                    if choice + 2 < CHOICES.len() {
+                        choice += 2;
+                        play_click_1();
+                    }
+                }
+            }
+            0x4b => {
+                // left
+                if !keystate_left {
+                    keystate_left = true;
+
+                    // change choice
+                    if choice % 2 == 1 {
+                        choice -= 1;
+                        play_click_1();
+                    }
+                }
+            }
+            0x4d => {
+                // right
+                if !keystate_right {
+                    keystate_right = true;
+
+                    // change choice
+                    if choice % 2 == 0 && choice + 1 < CHOICES.len() {
+                        choice += 1;
+                        play_click_1();
+                    }
                 }
             }
             0x1c => {
                 play_click_2();
-                return match choice {
-                    0 => MenuOutcome::Enter,
-                    1 => MenuOutcome::Exit,
-                    _ => unreachable!(),
-                };
+                return CHOICES[choice].1;
             }
             0x01 => {
                 // escape key
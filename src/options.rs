@@ -0,0 +1,328 @@
+//! The options screen
+
+use dos_x::{
+    key,
+    vga::{clear_screen, draw_rect, vsync, Palette},
+};
+
+use crate::{
+    audio::{
+        is_music_on, is_sound_on, play_click_1, play_click_2, set_music, set_sound, MAX_VOLUME,
+    },
+    check_saved,
+    config::{is_bindable, key_bindings, key_name, set_key_bindings, Config, KeyBindings},
+    gfx::{
+        apply_background, background, draw_arrow_left, draw_arrow_right, BACKGROUND_COLORS,
+        COLOR_BACKGROUND, COLOR_BLACK, COLOR_HIGHLIGHT, COLOR_WHITE,
+    },
+    global_hotkeys, wait_for_enter_release, Assets,
+};
+
+const BASE_Y: i32 = 44;
+const STRIDE: i32 = 16;
+const LABEL_X: i32 = 56;
+const VALUE_X: i32 = 192;
+
+const LABELS: [&str; 7] = [
+    "Music",
+    "Sound effects",
+    "Music volume",
+    "Background",
+    "Screenshot key",
+    "Music key",
+    "Sound key",
+];
+const MUSIC: usize = 0;
+const EFFECTS: usize = 1;
+const VOLUME: usize = 2;
+const BACKGROUND: usize = 3;
+/// the first of the rows for key bindings
+/// (screenshot, music, sound effects)
+const KEYS: usize = 4;
+/// the last row is for going back
+const DONE: usize = 7;
+
+/// Show the options screen,
+/// applying each change right away.
+///
+/// The options are written to the config file as the screen is left.
+pub fn options_screen(assets: &Assets, config: &mut Config, palette: &mut Palette) {
+    let Assets {
+        adlib_player,
+        big_font,
+        small_font,
+        ..
+    } = assets;
+
+    let initial_config = config.clone();
+
+    // draw the value of an option,
+    // or a prompt if a key is being picked for it
+    let draw_value = |row: usize, picking_key: bool| {
+        let y = BASE_Y + STRIDE * row as i32;
+        unsafe {
+            draw_rect(VALUE_X, y, 320 - VALUE_X as u32, 8, COLOR_BACKGROUND);
+        }
+        let on_off = |on: bool| if on { "On" } else { "Off" };
+        let keys = key_bindings();
+        let text = match row {
+            _ if picking_key => "Press a key",
+            MUSIC => on_off(is_music_on()),
+            EFFECTS => on_off(is_sound_on()),
+            VOLUME => {
+                // as a bar, one block for each step
+                for i in 0..MAX_VOLUME {
+                    let color = if i < adlib_player.volume() {
+                        COLOR_HIGHLIGHT
+                    } else {
+                        COLOR_WHITE
+                    };
+                    unsafe {
+                        draw_rect(VALUE_X + i as i32 * 8, y, 6, 8, color);
+                    }
+                }
+                return;
+            }
+            BACKGROUND => BACKGROUND_COLORS[background()].0,
+            _ => {
+                let key = [keys.screenshot, keys.music, keys.effects][row - KEYS];
+                key_name(key).unwrap_or_default()
+            }
+        };
+        let color = if picking_key {
+            COLOR_HIGHLIGHT
+        } else {
+            COLOR_BLACK
+        };
+        small_font.draw_text(VALUE_X, y, text, color);
+    };
+
+    unsafe {
+        vsync();
+        clear_screen(COLOR_BACKGROUND);
+    }
+    big_font.draw_text(99, 11, "Options", COLOR_WHITE);
+    big_font.draw_text(100, 12, "Options", COLOR_BLACK);
+    for (i, label) in LABELS.iter().enumerate() {
+        small_font.draw_text(LABEL_X, BASE_Y + STRIDE * i as i32, label, COLOR_BLACK);
+        draw_value(i, false);
+    }
+    let done_y = BASE_Y + STRIDE * DONE as i32 + 8;
+    small_font.draw_text(LABEL_X, done_y, "Done!", COLOR_BLACK);
+
+    // selector for the options, then Done!
+    let mut selector = 0;
+    // the key binding row waiting for a key, if any
+    let mut picking_key: Option<usize> = None;
+    // the key which was just bound, ignored until released
+    let mut bound_key: Option<u8> = None;
+    // what is on screen for music and sound effects,
+    // since these can also change with their hotkeys
+    let mut shown_sound = (is_music_on(), is_sound_on());
+
+    let mut keystate_up = false;
+    let mut keystate_down = false;
+    let mut keystate_left = false;
+    let mut keystate_right = false;
+    let mut keystate_enter = false;
+    // (ESC also cancels picking a key, but should not leave the screen then)
+    let mut keystate_escape = false;
+
+    wait_for_enter_release(assets);
+    loop {
+        unsafe {
+            vsync();
+        }
+
+        const ARROW_LEFT: u32 = 44;
+        const ARROW_RIGHT: u32 = 296;
+        // clear regions with selection arrow
+        unsafe {
+            let height = STRIDE as u32 * DONE as u32 + 16;
+            draw_rect(ARROW_LEFT as i32, BASE_Y, 7, height, COLOR_BACKGROUND);
+            draw_rect(ARROW_RIGHT as i32, BASE_Y, 7, height, COLOR_BACKGROUND);
+        }
+        let selection_y = BASE_Y as u32 + selector as u32 * STRIDE as u32;
+        let selection_y = if selector == DONE {
+            selection_y + 8
+        } else {
+            selection_y
+        };
+        draw_arrow_left(ARROW_LEFT, selection_y, COLOR_BLACK);
+        draw_arrow_right(ARROW_RIGHT, selection_y, COLOR_BLACK);
+
+        let key = key::get_keypress();
+
+        if let Some(row) = picking_key {
+            // bind the next key pressed, if it can be bound
+            if key == 0x01 {
+                picking_key = None;
+                keystate_escape = true;
+                play_click_1();
+                draw_value(row, false);
+            } else if key & 0x80 == 0 && is_bindable(key) {
+                picking_key = None;
+                play_click_2();
+                let keys = key_bindings();
+                let mut bindings = [keys.screenshot, keys.music, keys.effects];
+                let i = row - KEYS;
+                // a key already in use swaps places
+                if let Some(j) = bindings.iter().position(|&b| b == key) {
+                    bindings[j] = bindings[i];
+                }
+                bindings[i] = key;
+                let [screenshot, music, effects] = bindings;
+                config.keys = KeyBindings {
+                    screenshot,
+                    music,
+                    effects,
+                };
+                set_key_bindings(config.keys);
+                bound_key = Some(key);
+                for row in KEYS..DONE {
+                    draw_value(row, false);
+                }
+            }
+            adlib_player.poll(14_400);
+            continue;
+        }
+        if bound_key == Some(key) {
+            adlib_player.poll(14_400);
+            continue;
+        }
+        bound_key = None;
+
+        global_hotkeys(key);
+        // changing the option on the selected row (-1 or +1)
+        let mut change = 0;
+        match key {
+            // up
+            0x48 | 0x11 => {
+                if !keystate_up {
+                    keystate_up = true;
+                    if selector > 0 {
+                        selector -= 1;
+                        play_click_1();
+                    }
+                }
+            }
+            // up release
+            0xc8 | 0x91 => {
+                keystate_up = false;
+            }
+            // down
+            0x50 | 0x1f => {
+                if !keystate_down {
+                    keystate_down = true;
+                    if selector < DONE {
+                        selector += 1;
+                        play_click_1();
+                    }
+                }
+            }
+            // down release
+            0xd0 | 0x9f => {
+                keystate_down = false;
+            }
+
+            // left
+            0x4b | 0x1e => {
+                if !keystate_left {
+                    keystate_left = true;
+                    change = -1;
+                }
+            }
+            // left release
+            0xcb | 0x9e => {
+                keystate_left = false;
+            }
+
+            // right
+            0x4d | 0x20 => {
+                if !keystate_right {
+                    keystate_right = true;
+                    change = 1;
+                }
+            }
+            // right release
+            0xcd | 0xa0 => {
+                keystate_right = false;
+            }
+
+            // enter
+            0x1c => {
+                if !keystate_enter {
+                    keystate_enter = true;
+                    match selector {
+                        MUSIC | EFFECTS | BACKGROUND => change = 1,
+                        row @ KEYS..DONE => {
+                            play_click_2();
+                            picking_key = Some(row);
+                            draw_value(row, true);
+                        }
+                        DONE => break,
+                        _ => {}
+                    }
+                }
+            }
+            // enter release
+            0x9c => {
+                keystate_enter = false;
+            }
+
+            // escape release to go back to the main menu
+            0x81 => {
+                if !keystate_escape {
+                    break;
+                }
+                keystate_escape = false;
+            }
+
+            _ => {}
+        }
+
+        if change != 0 {
+            match selector {
+                MUSIC => {
+                    config.music = !is_music_on();
+                    set_music(config.music);
+                }
+                EFFECTS => {
+                    config.effects = !is_sound_on();
+                    set_sound(config.effects);
+                }
+                VOLUME => {
+                    let volume = adlib_player.volume() as i32 + change;
+                    config.volume = volume.clamp(0, MAX_VOLUME as i32) as u8;
+                    adlib_player.set_volume(config.volume);
+                }
+                BACKGROUND => {
+                    let num_colors = BACKGROUND_COLORS.len() as i32;
+                    let index = (background() as i32 + change).rem_euclid(num_colors);
+                    config.background = index as usize;
+                    apply_background(palette, config.background);
+                }
+                _ => {}
+            }
+            if selector < KEYS {
+                play_click_2();
+                draw_value(selector, false);
+            }
+        }
+
+        if shown_sound != (is_music_on(), is_sound_on()) {
+            // (also changed with the hotkeys, to be saved all the same)
+            shown_sound = (is_music_on(), is_sound_on());
+            (config.music, config.effects) = shown_sound;
+            draw_value(MUSIC, false);
+            draw_value(EFFECTS, false);
+        }
+
+        adlib_player.poll(14_400);
+    }
+
+    play_click_1();
+    if *config != initial_config {
+        check_saved(assets, config.save());
+    }
+}
@@ -0,0 +1,187 @@
+//! The body color picker screen
+
+use dos_x::{
+    key,
+    vga::{clear_screen, draw_rect, vsync, Palette},
+};
+
+use crate::{
+    audio::{play_click_1, play_click_2},
+    color::Hsv,
+    creature::CreatureParams,
+    gfx::{
+        draw_arrow_left, draw_arrow_right, set_creature_palette, COLOR_BACKGROUND, COLOR_BLACK,
+        COLOR_HIGHLIGHT,
+    },
+    global_hotkeys, Assets,
+};
+
+/// Pick any body color for the creature with HSV sliders.
+///
+/// The creature's palette is updated live.
+/// The screen is left through the Done! row.
+pub fn color_picker(assets: &Assets, creature: &mut CreatureParams, palette: &mut Palette) {
+    let Assets {
+        adlib_player,
+        creature_assets,
+        small_font,
+        ..
+    } = assets;
+
+    unsafe {
+        vsync();
+        clear_screen(COLOR_BACKGROUND);
+    }
+
+    let mut hsv = Hsv::from_rgb(creature.body_color());
+
+    small_font.draw_text(106, 16, "Custom color", COLOR_BLACK);
+    creature_assets.draw_creature(creature, 144, 36);
+
+    // the creature's color ramp (palette indices 3 to 6),
+    // which changes along with the sliders
+    for i in 0..4 {
+        unsafe {
+            draw_rect(112 + i * 24, 78, 24, 12, 3 + i as u8);
+        }
+    }
+
+    const BASE_Y: i32 = 104;
+    const STRIDE: i32 = 20;
+    const TRACK_X: i32 = 104;
+    const TRACK_WIDTH: u32 = 128;
+    // the last row is for the Done! action
+    const DONE: usize = 3;
+
+    small_font.draw_text(48, BASE_Y, "Hue", COLOR_BLACK);
+    small_font.draw_text(48, BASE_Y + STRIDE, "Sat", COLOR_BLACK);
+    small_font.draw_text(48, BASE_Y + STRIDE * 2, "Val", COLOR_BLACK);
+    small_font.draw_text(48, BASE_Y + STRIDE * 3 + 8, "Done!", COLOR_BLACK);
+
+    let mut selector = 0;
+    let mut redraw_sliders = true;
+
+    let mut keystate_up = false;
+    let mut keystate_down = false;
+    let mut keystate_enter = false;
+    // for how many frames left or right has been held
+    let mut held_frames = 0u32;
+
+    loop {
+        unsafe {
+            vsync();
+        }
+
+        const ARROW_LEFT: u32 = 28;
+        const ARROW_RIGHT: u32 = 252;
+        unsafe {
+            let height = STRIDE as u32 * DONE as u32 + 16;
+            draw_rect(ARROW_LEFT as i32, BASE_Y, 7, height, COLOR_BACKGROUND);
+            draw_rect(ARROW_RIGHT as i32, BASE_Y, 7, height, COLOR_BACKGROUND);
+        }
+        let selection_y = BASE_Y as u32 + selector as u32 * STRIDE as u32;
+        let selection_y = if selector == DONE {
+            selection_y + 9
+        } else {
+            selection_y
+        };
+        draw_arrow_left(ARROW_LEFT, selection_y, COLOR_BLACK);
+        draw_arrow_right(ARROW_RIGHT, selection_y, COLOR_BLACK);
+
+        if redraw_sliders {
+            let positions = [
+                hsv.h as u32 * TRACK_WIDTH / 360,
+                hsv.s as u32 * 2,
+                hsv.v as u32 * 2,
+            ];
+            for (i, pos) in positions.into_iter().enumerate() {
+                let y = BASE_Y + STRIDE * i as i32;
+                unsafe {
+                    // clear the previous knob, then draw track and knob
+                    draw_rect(TRACK_X - 1, y - 1, TRACK_WIDTH + 3, 9, COLOR_BACKGROUND);
+                    draw_rect(TRACK_X, y + 3, TRACK_WIDTH, 2, COLOR_BLACK);
+                    draw_rect(TRACK_X + pos as i32 - 1, y - 1, 3, 9, COLOR_HIGHLIGHT);
+                }
+            }
+            redraw_sliders = false;
+        }
+
+        // direction of the left or right key being held
+        let mut direction = 0;
+
+        let key = key::get_keypress();
+        global_hotkeys(key);
+        match key {
+            // up
+            0x48 | 0x11 => {
+                if !keystate_up {
+                    keystate_up = true;
+                    if selector > 0 {
+                        selector -= 1;
+                        play_click_1();
+                    }
+                }
+            }
+            0xc8 | 0x91 => {
+                keystate_up = false;
+            }
+            // down
+            0x50 | 0x1f => {
+                if !keystate_down {
+                    keystate_down = true;
+                    if selector < DONE {
+                        selector += 1;
+                        play_click_1();
+                    }
+                }
+            }
+            0xd0 | 0x9f => {
+                keystate_down = false;
+            }
+            // left and right
+            0x4b | 0x1e => direction = -1,
+            0x4d | 0x20 => direction = 1,
+
+            // enter
+            0x1c => {
+                if selector == DONE {
+                    keystate_enter = true;
+                }
+            }
+            // enter release
+            // (so that the editor does not see the key press)
+            0x9c => {
+                if keystate_enter {
+                    play_click_2();
+                    return;
+                }
+            }
+
+            _ => {}
+        }
+
+        // move once on press, then keep sliding while the key is held
+        let mut delta = 0;
+        if direction != 0 {
+            held_frames += 1;
+            if held_frames == 1 || (held_frames > 15 && held_frames.is_multiple_of(2)) {
+                delta = direction;
+            }
+        } else {
+            held_frames = 0;
+        }
+
+        if delta != 0 && selector < DONE {
+            match selector {
+                0 => hsv.h = (hsv.h as i32 + delta * 6).rem_euclid(360) as u16,
+                1 => hsv.s = (hsv.s as i32 + delta).clamp(0, 63) as u8,
+                _ => hsv.v = (hsv.v as i32 + delta).clamp(0, 63) as u8,
+            }
+            creature.set_custom_color(hsv.to_rgb());
+            set_creature_palette(palette, creature);
+            redraw_sliders = true;
+        }
+
+        adlib_player.poll(14_400);
+    }
+}
@@ -0,0 +1,147 @@
+//! The save file, with the player's collection of creatures
+//!
+//! The file starts with the magic bytes `CACS`,
+//! a format version byte and the number of creatures (u16, little endian).
+//! In version 1, each creature follows as:
+//!
+//! - the time it was saved (u32, little endian, packed FAT date and time)
+//! - the length of its creature code (u8)
+//! - the creature code (ASCII)
+//!
+//! Creature codes have their own version and checksum,
+//! so creatures with damaged codes are skipped
+//! without losing the rest of the collection.
+
+use alloc::vec::Vec;
+use core::ffi::CStr;
+
+use crate::{
+    creature::{CreatureParams, CODE_LEN},
+    file::{read_file, rename_file, replace_file},
+    time::Timestamp,
+};
+
+const SAVE_FILE: &CStr = c"CREATEAC.SAV";
+/// where the save file goes while it is being written
+const SAVE_TEMP_FILE: &CStr = c"CREATSAV.TMP";
+/// where an unreadable save file is moved to,
+/// instead of overwriting it
+const SAVE_BACKUP_FILE: &CStr = c"CREATEAC.BAK";
+
+const SAVE_MAGIC: &[u8; 4] = b"CACS";
+/// the current version of the save file format
+const SAVE_VERSION: u8 = 1;
+/// the size of the header (magic, version, number of creatures)
+const HEADER_LEN: usize = 4 + 1 + 2;
+
+/// A creature in the player's collection
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SavedCreature {
+    pub creature: CreatureParams,
+    /// when the creature was saved
+    pub saved_at: Timestamp,
+}
+
+/// The player's collection of creatures, as kept in the save file
+#[derive(Debug)]
+pub struct SaveFile {
+    pub creatures: Vec<SavedCreature>,
+    /// whether the collection can be written to disk
+    /// (not the case if the save file is from a newer version of the game)
+    writable: bool,
+}
+
+impl SaveFile {
+    /// Load the collection from the save file.
+    ///
+    /// The collection starts empty if there is no save file.
+    /// A save file which cannot be read at all
+    /// is kept aside as `CREATEAC.BAK`,
+    /// and one from a newer version of the game is left untouched.
+    pub fn load() -> Self {
+        let mut save = SaveFile {
+            creatures: Vec::new(),
+            writable: true,
+        };
+        let Some(data) = read_file(SAVE_FILE) else {
+            return save;
+        };
+
+        if data.len() < HEADER_LEN || &data[..4] != SAVE_MAGIC {
+            // not a save file, or too damaged to tell
+            rename_file(SAVE_FILE, SAVE_BACKUP_FILE);
+            return save;
+        }
+        let count = u16::from_le_bytes([data[5], data[6]]);
+        let records = &data[HEADER_LEN..];
+        match data[4] {
+            1 => save.creatures = read_records_v1(records, count),
+            v if v > SAVE_VERSION => {
+                // written by a newer game, do not overwrite it
+                save.writable = false;
+            }
+            _ => {
+                rename_file(SAVE_FILE, SAVE_BACKUP_FILE);
+            }
+        }
+        save
+    }
+
+    /// Add a creature to the collection and write it to disk,
+    /// returning whether the save file was written.
+    pub fn append(&mut self, creature: &CreatureParams) -> bool {
+        self.creatures.push(SavedCreature {
+            creature: *creature,
+            saved_at: Timestamp::now(),
+        });
+        self.save()
+    }
+
+    /// Write the whole collection to disk,
+    /// returning whether it was successful.
+    pub fn save(&self) -> bool {
+        if !self.writable {
+            return false;
+        }
+        // (the count is u16, so keep only the first creatures if there are more)
+        let creatures = &self.creatures[..self.creatures.len().min(u16::MAX as usize)];
+
+        let mut data = Vec::with_capacity(HEADER_LEN + creatures.len() * (5 + CODE_LEN));
+        data.extend_from_slice(SAVE_MAGIC);
+        data.push(SAVE_VERSION);
+        data.extend_from_slice(&(creatures.len() as u16).to_le_bytes());
+        for saved in creatures {
+            let code = saved.creature.to_code();
+            data.extend_from_slice(&saved.saved_at.to_fat().to_le_bytes());
+            data.push(code.len() as u8);
+            data.extend_from_slice(code.as_bytes());
+        }
+        replace_file(SAVE_FILE, SAVE_TEMP_FILE, &data)
+    }
+}
+
+/// read up to `count` creatures in the version 1 format,
+/// stopping early if the file was cut short
+fn read_records_v1(mut records: &[u8], count: u16) -> Vec<SavedCreature> {
+    let mut creatures = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let [t0, t1, t2, t3, len, rest @ ..] = records else {
+            break;
+        };
+        let Some(code) = rest.get(..*len as usize) else {
+            break;
+        };
+        records = &rest[*len as usize..];
+
+        let Ok(code) = core::str::from_utf8(code) else {
+            continue;
+        };
+        if let Ok(creature) = CreatureParams::from_code(code) {
+            creatures.push(SavedCreature {
+                creature,
+                saved_at: Timestamp::from_fat(u32::from_le_bytes([*t0, *t1, *t2, *t3])),
+            });
+        }
+    }
+    creatures
+}
@@ -0,0 +1,77 @@
+//! Screenshots of the whole screen, taken with F12 (or another bound key) in any scene
+
+use alloc::ffi::CString;
+use alloc::format;
+use alloc::vec;
+use dos_x::djgpp::pc::{inportb, outportb};
+use dos_x::vga::read_video_buffer_rect;
+
+use crate::{
+    audio::play_click_2,
+    config::key_bindings,
+    file::{file_exists, write_file},
+    gfx::encode_pcx,
+};
+
+/// whether the screenshot key is being held
+static mut KEYSTATE_SCREENSHOT: bool = false;
+
+/// the next screenshot number to try
+static mut NEXT_SHOT: u16 = 0;
+
+/// Take a screenshot if the screenshot key (F12 by default) has just been pressed.
+///
+/// Called by `global_hotkeys` with the key read in each frame.
+pub fn screenshot_hotkey(key: u8) {
+    let binding = key_bindings().screenshot;
+    unsafe {
+        if key == binding {
+            if !KEYSTATE_SCREENSHOT {
+                KEYSTATE_SCREENSHOT = true;
+                if take_screenshot() {
+                    play_click_2();
+                }
+            }
+        } else if key == binding | 0x80 {
+            // release
+            KEYSTATE_SCREENSHOT = false;
+        }
+    }
+}
+
+/// Save the whole screen with the current palette
+/// to the next free `SHOTnnnn.PCX`,
+/// returning whether it was saved.
+pub fn take_screenshot() -> bool {
+    let mut pixels = vec![0u8; 320 * 200];
+    unsafe {
+        read_video_buffer_rect(&mut pixels, (0, 0), (320, 200));
+    }
+    let palette = read_dac_palette();
+    let data = encode_pcx(&pixels, 320, 200, 1, &palette);
+
+    unsafe {
+        while NEXT_SHOT < 10_000 {
+            let shot = NEXT_SHOT;
+            NEXT_SHOT += 1;
+            let path = CString::new(format!("SHOT{shot:04}.PCX")).unwrap();
+            if !file_exists(&path) {
+                return write_file(&path, &data);
+            }
+        }
+    }
+    false
+}
+
+/// read the palette currently in use from the VGA DAC
+fn read_dac_palette() -> [u8; 768] {
+    let mut palette = [0; 768];
+    unsafe {
+        // start reading at color 0, the index moves on by itself
+        outportb(0x3c7, 0);
+        for c in palette.iter_mut() {
+            *c = inportb(0x3c9) & 0x3f;
+        }
+    }
+    palette
+}
@@ -0,0 +1,82 @@
+//! Date and time from the DOS clock
+
+use dos_x::djgpp::dpmi::{__dpmi_int, __dpmi_regs};
+
+/// A date and time as kept by DOS,
+/// with a resolution of 2 seconds
+/// so that it fits in the packed FAT format
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Timestamp {
+    /// the year (1980 to 2107)
+    pub year: u16,
+    /// the month (1 to 12)
+    pub month: u8,
+    /// the day of the month (1 to 31)
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    /// seconds (always even)
+    pub second: u8,
+}
+
+impl Timestamp {
+    /// Read the current date and time from DOS.
+    pub fn now() -> Self {
+        unsafe {
+            // get date: CX = year, DH = month, DL = day
+            let mut regs: __dpmi_regs = core::mem::zeroed();
+            regs.h.ah = 0x2a;
+            __dpmi_int(0x21, &mut regs);
+            let year = regs.x.cx;
+            let month = regs.h.dh;
+            let day = regs.h.dl;
+
+            // get time: CH = hour, CL = minutes, DH = seconds
+            let mut regs: __dpmi_regs = core::mem::zeroed();
+            regs.h.ah = 0x2c;
+            __dpmi_int(0x21, &mut regs);
+
+            Timestamp {
+                year,
+                month,
+                day,
+                hour: regs.h.ch,
+                minute: regs.h.cl,
+                second: regs.h.dh & !1,
+            }
+        }
+    }
+
+    /// Pack the timestamp in the FAT format
+    /// (date in the high 16 bits, time in the low 16 bits).
+    pub fn to_fat(self) -> u32 {
+        let date = (self.year.saturating_sub(1980).min(127) as u32) << 9
+            | (self.month as u32 & 0xf) << 5
+            | (self.day as u32 & 0x1f);
+        let time = (self.hour as u32 & 0x1f) << 11
+            | (self.minute as u32 & 0x3f) << 5
+            | ((self.second as u32 / 2) & 0x1f);
+        date << 16 | time
+    }
+
+    /// Unpack a timestamp in the FAT format.
+    pub fn from_fat(fat: u32) -> Self {
+        let date = fat >> 16;
+        let time = fat & 0xffff;
+        Timestamp {
+            year: 1980 + (date >> 9) as u16,
+            month: (date >> 5 & 0xf) as u8,
+            day: (date & 0x1f) as u8,
+            hour: (time >> 11) as u8,
+            minute: (time >> 5 & 0x3f) as u8,
+            second: (time & 0x1f) as u8 * 2,
+        }
+    }
+}
+
+/// Prints the date as in `2025-12-24`
+impl core::fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}